@@ -0,0 +1,39 @@
+//! Runs the test command of an automated `git bisect run` from a chosen
+//! directory.
+//!
+//! Git always starts the command from the top of the worktree, so when a run
+//! configuration names another directory the app passes its own executable as
+//! `<exe> bisect-exec <cwd> <command> [args...]`. That sets the working
+//! directory of the real command directly, without relying on a shell.
+
+use std::process::Command;
+
+pub const EXEC_ARG: &str = "bisect-exec";
+/// Exit code that makes `git bisect run` abort instead of marking a commit.
+pub const ABORT_CODE: i32 = 128;
+
+/// Handles `bisect-exec` invocations. Returns the test command's exit code
+/// when `args` describe one and `None` when the app should start normally.
+pub fn run_from_args(args: &[String]) -> Option<i32> {
+    if args.get(1)? != EXEC_ARG {
+        return None;
+    }
+    let (Some(cwd), Some(program)) = (args.get(2), args.get(3)) else {
+        eprintln!("projectlib bisect: expected `{EXEC_ARG} <cwd> <command> [args...]`");
+        return Some(ABORT_CODE);
+    };
+
+    let status = Command::new(program)
+        .args(&args[4..])
+        .current_dir(cwd)
+        .status();
+    Some(match status {
+        // A command killed by a signal has no exit code; git cannot tell
+        // whether the commit is good, so the bisect stops.
+        Ok(status) => status.code().unwrap_or(ABORT_CODE),
+        Err(err) => {
+            eprintln!("projectlib bisect: failed to run `{program}` in {cwd}: {err}");
+            ABORT_CODE
+        }
+    })
+}
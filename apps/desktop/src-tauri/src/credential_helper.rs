@@ -0,0 +1,125 @@
+//! Client side of the git credential helper served by the desktop app.
+//!
+//! Git invokes the app executable as `<exe> git-credential-helper <get|store|erase>`
+//! and ssh invokes it (through a small wrapper script) as `<exe> ssh-askpass <prompt>`.
+//! Both forward the request over the local socket advertised in the environment
+//! together with a per-command session token, so secrets never appear in the
+//! child environment.
+
+use std::io::{self, Read, Write};
+
+pub const HELPER_ARG: &str = "git-credential-helper";
+pub const ASKPASS_ARG: &str = "ssh-askpass";
+pub const ENDPOINT_ENV: &str = "PROJECTLIB_CREDENTIAL_ENDPOINT";
+pub const TOKEN_ENV: &str = "PROJECTLIB_CREDENTIAL_TOKEN";
+
+/// Handles helper invocations. Returns the process exit code when `args`
+/// describe a helper call and `None` when the app should start normally.
+pub fn run_from_args(args: &[String]) -> Option<i32> {
+    let mode = args.get(1)?;
+    let result = if mode == HELPER_ARG {
+        let operation = args.get(2).map(String::as_str).unwrap_or("get");
+        run_credential_operation(operation)
+    } else if mode == ASKPASS_ARG {
+        let prompt = args.get(2).map(String::as_str).unwrap_or("");
+        run_askpass(prompt)
+    } else {
+        return None;
+    };
+
+    Some(match result {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("projectlib credential helper: {err}");
+            1
+        }
+    })
+}
+
+/// Parses git credential attributes (`key=value` lines, terminated by a blank line).
+pub fn parse_attributes(input: &str) -> Vec<(String, String)> {
+    input
+        .lines()
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+pub fn format_attributes(attributes: &[(String, String)]) -> String {
+    let mut output = String::new();
+    for (key, value) in attributes {
+        if key.contains(['=', '\n']) || value.contains(['\n', '\0']) {
+            continue;
+        }
+        output.push_str(key);
+        output.push('=');
+        output.push_str(value);
+        output.push('\n');
+    }
+    output
+}
+
+pub fn attribute<'a>(attributes: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.as_str())
+}
+
+fn run_credential_operation(operation: &str) -> io::Result<()> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+
+    let mut request = session_attributes(operation)?;
+    request.extend(parse_attributes(&input));
+    let response = exchange(&request)?;
+
+    if operation == "get" {
+        io::stdout().write_all(format_attributes(&response).as_bytes())?;
+    }
+    Ok(())
+}
+
+fn run_askpass(prompt: &str) -> io::Result<()> {
+    let mut request = session_attributes("askpass")?;
+    request.push(("prompt".into(), prompt.replace('\n', " ")));
+    let response = exchange(&request)?;
+
+    let value = attribute(&response, "password")
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no passphrase available"))?;
+    writeln!(io::stdout(), "{value}")
+}
+
+fn session_attributes(operation: &str) -> io::Result<Vec<(String, String)>> {
+    let token = std::env::var(TOKEN_ENV)
+        .map_err(|_| io::Error::new(io::ErrorKind::NotFound, "no credential session"))?;
+    Ok(vec![
+        ("token".into(), token),
+        ("operation".into(), operation.into()),
+    ])
+}
+
+fn exchange(request: &[(String, String)]) -> io::Result<Vec<(String, String)>> {
+    let endpoint = std::env::var(ENDPOINT_ENV)
+        .map_err(|_| io::Error::new(io::ErrorKind::NotFound, "no credential endpoint"))?;
+
+    let mut stream = connect(&endpoint)?;
+    stream.write_all(format_attributes(request).as_bytes())?;
+    stream.write_all(b"\n")?;
+    stream.flush()?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    Ok(parse_attributes(&response))
+}
+
+#[cfg(unix)]
+fn connect(endpoint: &str) -> io::Result<std::os::unix::net::UnixStream> {
+    std::os::unix::net::UnixStream::connect(endpoint)
+}
+
+#[cfg(not(unix))]
+fn connect(endpoint: &str) -> io::Result<std::net::TcpStream> {
+    std::net::TcpStream::connect(endpoint)
+}
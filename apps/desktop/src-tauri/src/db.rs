@@ -0,0 +1,76 @@
+use crate::{
+    error::AppError,
+    migrations::{self, MigrationKind},
+};
+use sqlx::{
+    error::BoxDynError,
+    migrate::{Migration, MigrationSource, MigrationType, Migrator},
+    sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions},
+};
+use std::{future::Future, pin::Pin, str::FromStr, time::Duration};
+
+/// The app's migrations in the form the SQL plugin hands them to sqlx, so
+/// both record the same checksums and never apply a migration twice.
+#[derive(Debug)]
+struct Definitions;
+
+impl MigrationSource<'static> for Definitions {
+    fn resolve(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<Migration>, BoxDynError>> + Send + 'static>> {
+        Box::pin(async move {
+            Ok(migrations::definitions()
+                .into_iter()
+                .filter(|migration| migration.kind == MigrationKind::Up)
+                .map(|migration| {
+                    Migration::new(
+                        migration.version,
+                        migration.description.into(),
+                        MigrationType::ReversibleUp,
+                        migration.sql.into(),
+                        false,
+                    )
+                })
+                .collect())
+        })
+    }
+}
+
+/// Backend connection pool for `projectlib.db`. The front-end keeps going
+/// through the SQL plugin; both share the same file and migrations.
+pub struct Database {
+    pool: SqlitePool,
+}
+
+impl Database {
+    /// Opens the pool without connecting; connections are established on
+    /// first use. Must be called from within the async runtime.
+    pub fn connect_lazy(url: &str) -> Result<Self, AppError> {
+        let options = SqliteConnectOptions::from_str(url)
+            .map_err(|err| AppError::Db(err.to_string()))?
+            .create_if_missing(true)
+            .foreign_keys(true)
+            .busy_timeout(Duration::from_secs(5));
+        let pool = SqlitePoolOptions::new()
+            .max_connections(4)
+            .connect_lazy_with(options);
+        Ok(Self { pool })
+    }
+
+    /// Applies pending migrations. Backend commands may run before the
+    /// front-end loads the database through the SQL plugin, so the tables
+    /// they use have to exist from startup.
+    pub async fn migrate(&self) -> Result<(), AppError> {
+        let migrator = Migrator::new(Definitions)
+            .await
+            .map_err(|err| AppError::Db(err.to_string()))?;
+        migrator
+            .run(&self.pool)
+            .await
+            .map_err(|err| AppError::Db(err.to_string()))
+    }
+
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+}
@@ -0,0 +1,122 @@
+use crate::git::{
+    models::{
+        GitArchiveRequest, GitCommandHandle, GitErrorKind, GitErrorResponse, GitStreamRequest,
+    },
+    operations::run_git_capture,
+    service::GitService,
+    streaming::run_streaming_command,
+    util,
+};
+use std::path::Path;
+use tauri::{AppHandle, State};
+
+fn invalid(message: String) -> GitErrorResponse {
+    GitErrorResponse::new(GitErrorKind::InvalidArgument, message)
+}
+
+/// Writes `revision` to an archive without checking it out. Each archived
+/// path is reported on stderr (`-v`), streamed as `git://stream` events.
+#[tauri::command]
+pub async fn git_archive(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitArchiveRequest,
+) -> Result<GitCommandHandle, GitErrorResponse> {
+    let revision =
+        util::sanitize_revision(&request.revision, "revision").map_err(GitErrorResponse::from)?;
+
+    let output = Path::new(request.output_path.trim());
+    if !output.is_absolute() {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidPath,
+            "the archive path must be absolute",
+        ));
+    }
+    if !output.parent().is_some_and(Path::is_dir) {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidPath,
+            format!(
+                "{} does not exist",
+                output.parent().unwrap_or(output).display()
+            ),
+        ));
+    }
+    if output.exists() && !request.overwrite.unwrap_or(false) {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidPath,
+            format!("{} already exists", output.display()),
+        ));
+    }
+
+    // Fail here rather than in the stream when the revision is unknown.
+    let tree = run_git_capture(
+        &app,
+        &service,
+        &request.repository_path,
+        vec![
+            "rev-parse".into(),
+            "--verify".into(),
+            "--quiet".into(),
+            format!("{revision}^{{tree}}"),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !tree.success {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::RefNotFound,
+            format!("`{revision}` does not name a commit or tree"),
+        ));
+    }
+
+    let mut args = vec![
+        "archive".into(),
+        "-v".into(),
+        request.format.as_arg().into(),
+        format!("--output={}", output.display()),
+    ];
+    if let Some(prefix) = request.prefix.as_deref().map(str::trim) {
+        if !prefix.is_empty() {
+            let prefix = util::sanitize_arg(prefix.trim_start_matches('/'), "prefix")
+                .map_err(GitErrorResponse::from)?;
+            if prefix.split('/').any(|part| part == "..") {
+                return Err(invalid(format!(
+                    "prefix may not contain `..`, got `{prefix}`"
+                )));
+            }
+            let separator = if prefix.ends_with('/') { "" } else { "/" };
+            args.push(format!("--prefix={prefix}{separator}"));
+        }
+    }
+    args.push(revision);
+    if let Some(subdirectory) = request.subdirectory.as_deref().map(str::trim) {
+        let subdirectory = subdirectory.replace('\\', "/");
+        let subdirectory = subdirectory.trim_matches('/');
+        if !subdirectory.is_empty() {
+            if subdirectory.split('/').any(|part| part == "..") {
+                return Err(invalid(format!(
+                    "subdirectory may not contain `..`, got `{subdirectory}`"
+                )));
+            }
+            args.push("--".into());
+            args.push(
+                util::sanitize_arg(subdirectory, "subdirectory").map_err(GitErrorResponse::from)?,
+            );
+        }
+    }
+
+    run_streaming_command(
+        app,
+        service,
+        GitStreamRequest {
+            repository_path: request.repository_path,
+            remote: None,
+            branch: None,
+            auth: None,
+            command_id: request.command_id,
+        },
+        args,
+    )
+    .await
+}
@@ -0,0 +1,50 @@
+use crate::git::{
+    models::{GitErrorResponse, GitFileAttributes, GitPathsRequest},
+    operations::run_git_capture,
+    service::GitService,
+    util,
+};
+use tauri::{AppHandle, State};
+
+/// Effective `.gitattributes` for each path (eol, diff driver, binary, LFS).
+/// Paths without any specified attribute are returned with defaults.
+#[tauri::command]
+pub async fn git_check_attr(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitPathsRequest,
+) -> Result<Vec<GitFileAttributes>, GitErrorResponse> {
+    if request.paths.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut args = vec!["check-attr".into(), "-a".into(), "-z".into(), "--".into()];
+    for path in request.paths.iter() {
+        args.push(util::sanitize_arg(path, "path").map_err(GitErrorResponse::from)?);
+    }
+
+    let outcome = run_git_capture(&app, &service, &request.repository_path, args, None)
+        .await
+        .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to read attributes",
+        ));
+    }
+
+    let mut found = util::parse_check_attr(&outcome.stdout);
+    Ok(request
+        .paths
+        .into_iter()
+        .map(
+            |path| match found.iter().position(|file| file.path == path) {
+                Some(idx) => found.swap_remove(idx),
+                None => GitFileAttributes {
+                    path,
+                    ..Default::default()
+                },
+            },
+        )
+        .collect())
+}
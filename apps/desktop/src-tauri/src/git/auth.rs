@@ -1,4 +1,11 @@
-use crate::git::models::GitError;
+use crate::{
+    credential_helper,
+    git::{
+        credential_server::{CredentialServer, HelperSession, SessionGuard},
+        models::GitError,
+        util,
+    },
+};
 use serde::Deserialize;
 use std::{collections::HashMap, fmt::Write as _};
 use tempfile::{Builder, TempPath};
@@ -9,20 +16,35 @@ pub enum GitAuth {
     Token {
         token: String,
         username: Option<String>,
+        remember: Option<bool>,
     },
     UserPassword {
         username: String,
         password: String,
+        remember: Option<bool>,
     },
     SshCommand {
         command: String,
     },
+    SshKey {
+        key_path: String,
+        passphrase: Option<String>,
+    },
+}
+
+/// Resources that must outlive the git process they were prepared for; they
+/// are only held so that dropping them cleans up.
+#[derive(Debug)]
+#[allow(dead_code)]
+pub enum AuthGuard {
+    Script(TempPath),
+    Session(SessionGuard),
 }
 
 #[derive(Debug, Default)]
 pub struct PreparedAuth {
     pub env: HashMap<String, String>,
-    pub cleanup: Vec<TempPath>,
+    pub cleanup: Vec<AuthGuard>,
 }
 
 impl PreparedAuth {
@@ -32,113 +54,276 @@ impl PreparedAuth {
             cleanup: Vec::new(),
         }
     }
+
+    fn with_session(
+        server: &CredentialServer,
+        mut session: HelperSession,
+        hosts: &[String],
+        exclusive: bool,
+    ) -> Result<Self, GitError> {
+        session.hosts = hosts.to_vec();
+        let guard = server.open_session(session);
+        let env = server.helper_env(&guard, exclusive)?;
+        Ok(Self {
+            env,
+            cleanup: vec![AuthGuard::Session(guard)],
+        })
+    }
+}
+
+/// Subcommands that may contact a remote and so need the credential helper.
+const NETWORK_SUBCOMMANDS: &[&str] = &[
+    "clone",
+    "fetch",
+    "lfs",
+    "ls-remote",
+    "pull",
+    "push",
+    "remote",
+    "submodule",
+];
+
+/// Whether the git invocation `args` may reach the network.
+pub fn is_network_command(args: &[String]) -> bool {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "-c" || arg == "-C" {
+            iter.next();
+        } else if !arg.starts_with('-') {
+            return NETWORK_SUBCOMMANDS.contains(&arg.as_str());
+        }
+    }
+    false
+}
+
+/// Hosts a credential session for `args` is limited to: those of the remotes
+/// or URLs the command names, or else of every remote in `remotes`, given as
+/// `(name, url)` pairs.
+pub fn session_hosts(args: &[String], remotes: &[(String, String)]) -> Vec<String> {
+    let named = remotes
+        .iter()
+        .filter(|(name, _)| args.iter().any(|arg| arg == name))
+        .map(|(_, url)| url.as_str());
+    // `scheme://` URLs and scp-like `user@host:path`; bare `a:b` arguments
+    // are usually refspecs.
+    let urls = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| !arg.starts_with('-'))
+        .filter(|arg| arg.contains("://") || (arg.contains('@') && arg.contains(':')));
+
+    let mut hosts: Vec<String> = named.chain(urls).filter_map(util::remote_host).collect();
+    if hosts.is_empty() {
+        hosts = remotes
+            .iter()
+            .filter_map(|(_, url)| util::remote_host(url))
+            .collect();
+    }
+    hosts.sort();
+    hosts.dedup();
+    hosts
+}
+
+/// Prepares the environment for one git invocation that may contact `hosts`.
+/// Without explicit `auth` the credential helper answers from the credential
+/// store, but only for those hosts; commands that reach no remote get no
+/// helper session at all.
+pub fn prepare_auth(
+    auth: Option<&GitAuth>,
+    server: &CredentialServer,
+    hosts: &[String],
+) -> Result<PreparedAuth, GitError> {
+    match auth {
+        Some(auth) => auth.prepare(server, hosts),
+        None if hosts.is_empty() => Ok(PreparedAuth::empty()),
+        None => Ok(
+            PreparedAuth::with_session(server, HelperSession::default(), hosts, false)
+                .unwrap_or_else(|_| PreparedAuth::empty()),
+        ),
+    }
 }
 
 impl GitAuth {
-    pub fn prepare(&self) -> Result<PreparedAuth, GitError> {
+    pub fn prepare(
+        &self,
+        server: &CredentialServer,
+        hosts: &[String],
+    ) -> Result<PreparedAuth, GitError> {
         match self {
-            GitAuth::Token { token, username } => {
+            GitAuth::Token {
+                token,
+                username,
+                remember,
+            } => {
                 let username = username.clone().unwrap_or_else(|| "git".to_string());
-                Self::prepare_askpass(&username, token)
-            }
-            GitAuth::UserPassword { username, password } => {
-                Self::prepare_askpass(username, password)
+                Self::prepare_credentials(
+                    server,
+                    hosts,
+                    &username,
+                    token,
+                    remember.unwrap_or(false),
+                )
             }
+            GitAuth::UserPassword {
+                username,
+                password,
+                remember,
+            } => Self::prepare_credentials(
+                server,
+                hosts,
+                username,
+                password,
+                remember.unwrap_or(false),
+            ),
             GitAuth::SshCommand { command } => {
                 if command.trim().is_empty() {
                     return Err(GitError::InvalidArgument(
                         "SSH command override must not be empty".into(),
                     ));
                 }
-                let mut env = HashMap::new();
-                env.insert("GIT_SSH_COMMAND".into(), command.clone());
-                Ok(PreparedAuth {
-                    env,
-                    cleanup: vec![],
-                })
+                let mut prepared = prepare_auth(None, server, hosts)?;
+                prepared
+                    .env
+                    .insert("GIT_SSH_COMMAND".into(), command.clone());
+                Ok(prepared)
             }
+            GitAuth::SshKey {
+                key_path,
+                passphrase,
+            } => Self::prepare_ssh_key(server, hosts, key_path, passphrase.as_deref()),
         }
     }
 
-    fn prepare_askpass(username: &str, secret: &str) -> Result<PreparedAuth, GitError> {
+    fn prepare_credentials(
+        server: &CredentialServer,
+        hosts: &[String],
+        username: &str,
+        secret: &str,
+        remember: bool,
+    ) -> Result<PreparedAuth, GitError> {
         if username.contains('\0') || secret.contains('\0') {
             return Err(GitError::InvalidArgument(
                 "credential values may not contain null bytes".into(),
             ));
         }
 
-        let mut env = HashMap::new();
-        env.insert("TAURI_GIT_USERNAME".into(), username.to_string());
-        env.insert("TAURI_GIT_PASSWORD".into(), secret.to_string());
-        env.insert("GIT_TERMINAL_PROMPT".into(), "0".into());
-
-        #[cfg(target_os = "windows")]
-        let (script, temp_path) = Self::windows_script()?;
-        #[cfg(not(target_os = "windows"))]
-        let (script, temp_path) = Self::unix_script()?;
+        let session = HelperSession {
+            username: Some(username.to_string()),
+            password: Some(secret.to_string()),
+            passphrase: None,
+            remember,
+            hosts: Vec::new(),
+        };
+        PreparedAuth::with_session(server, session, hosts, true)
+    }
 
-        std::fs::write(&script, Self::askpass_contents())?;
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let mut perms = std::fs::metadata(&script)?.permissions();
-            perms.set_mode(0o700);
-            std::fs::set_permissions(&script, perms)?;
+    fn prepare_ssh_key(
+        server: &CredentialServer,
+        hosts: &[String],
+        key_path: &str,
+        passphrase: Option<&str>,
+    ) -> Result<PreparedAuth, GitError> {
+        if key_path.trim().is_empty() {
+            return Err(GitError::InvalidArgument(
+                "SSH key path must not be empty".into(),
+            ));
+        }
+        if key_path.contains('\0') || passphrase.is_some_and(|p| p.contains('\0')) {
+            return Err(GitError::InvalidArgument(
+                "SSH key values may not contain null bytes".into(),
+            ));
+        }
+        if !std::path::Path::new(key_path).is_file() {
+            return Err(GitError::InvalidPath(format!(
+                "SSH key `{key_path}` does not exist"
+            )));
         }
 
-        env.insert("GIT_ASKPASS".into(), script.to_string_lossy().to_string());
+        let mut command = format!(
+            "ssh -i {} -o IdentitiesOnly=yes -o StrictHostKeyChecking=yes",
+            shell_quote(key_path)
+        );
 
-        Ok(PreparedAuth {
-            env,
-            cleanup: vec![temp_path],
-        })
-    }
+        let mut prepared = match passphrase {
+            Some(passphrase) => {
+                let session = HelperSession {
+                    passphrase: Some(passphrase.to_string()),
+                    ..HelperSession::default()
+                };
+                let mut prepared = PreparedAuth::with_session(server, session, hosts, false)?;
+                let (script, temp_path) = Self::askpass_script()?;
+                prepared.env.insert("SSH_ASKPASS".into(), script);
+                prepared
+                    .env
+                    .insert("SSH_ASKPASS_REQUIRE".into(), "force".into());
+                prepared.cleanup.push(AuthGuard::Script(temp_path));
+                prepared
+            }
+            None => {
+                command.push_str(" -o BatchMode=yes");
+                prepare_auth(None, server, hosts)?
+            }
+        };
 
-    #[cfg(not(target_os = "windows"))]
-    fn unix_script() -> Result<(std::path::PathBuf, TempPath), GitError> {
-        let file = Builder::new().prefix("projectlib-askpass-").tempfile()?;
-        let path = file.path().to_path_buf();
-        Ok((path, file.into_temp_path()))
+        prepared.env.insert("GIT_SSH_COMMAND".into(), command);
+        Ok(prepared)
     }
 
-    #[cfg(target_os = "windows")]
-    fn windows_script() -> Result<(std::path::PathBuf, TempPath), GitError> {
+    /// Writes a wrapper for `SSH_ASKPASS`, which must name a program without
+    /// arguments; it only re-invokes the app in askpass mode.
+    fn askpass_script() -> Result<(String, TempPath), GitError> {
+        let executable = std::env::current_exe()?;
+
+        #[cfg(target_os = "windows")]
         let file = Builder::new()
             .prefix("projectlib-askpass-")
             .suffix(".cmd")
             .tempfile()?;
-        let path = file.path().to_path_buf();
-        Ok((path, file.into_temp_path()))
-    }
+        #[cfg(not(target_os = "windows"))]
+        let file = Builder::new().prefix("projectlib-askpass-").tempfile()?;
 
-    fn askpass_contents() -> String {
+        let mut content = String::new();
         #[cfg(target_os = "windows")]
         {
-            let mut content = String::new();
             writeln!(&mut content, "@echo off").unwrap();
-            writeln!(&mut content, "set prompt=%1").unwrap();
-            writeln!(&mut content, "echo %prompt%| findstr /I \"Username\" >nul").unwrap();
-            writeln!(&mut content, "if %errorlevel%==0 (").unwrap();
-            writeln!(&mut content, "  echo %TAURI_GIT_USERNAME%").unwrap();
-            writeln!(&mut content, ") else (").unwrap();
-            writeln!(&mut content, "  echo %TAURI_GIT_PASSWORD%").unwrap();
-            writeln!(&mut content, ")").unwrap();
-            content
+            writeln!(
+                &mut content,
+                "\"{}\" {} %*",
+                executable.to_string_lossy(),
+                credential_helper::ASKPASS_ARG
+            )
+            .unwrap();
         }
         #[cfg(not(target_os = "windows"))]
         {
-            let mut content = String::new();
             writeln!(&mut content, "#!/bin/sh").unwrap();
             writeln!(
                 &mut content,
-                "case \"$1\" in\n  *Username* ) printf '%s\\n' \"$TAURI_GIT_USERNAME\" ;;\n  *username* ) printf '%s\\n' \"$TAURI_GIT_USERNAME\" ;;\n  * ) printf '%s\\n' \"$TAURI_GIT_PASSWORD\" ;;\nesac"
+                "exec {} {} \"$@\"",
+                shell_quote(&executable.to_string_lossy()),
+                credential_helper::ASKPASS_ARG
             )
             .unwrap();
-            content
         }
+
+        let path = file.path().to_path_buf();
+        std::fs::write(&path, content)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = std::fs::metadata(&path)?.permissions();
+            perms.set_mode(0o700);
+            std::fs::set_permissions(&path, perms)?;
+        }
+
+        Ok((path.to_string_lossy().to_string(), file.into_temp_path()))
     }
 }
 
+pub(crate) fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
 pub fn merge_auth_env(
     mut base: HashMap<String, String>,
     extra: &HashMap<String, String>,
@@ -149,7 +334,7 @@ pub fn merge_auth_env(
     base
 }
 
-pub fn collect_cleanup(mut existing: Vec<TempPath>, mut extra: Vec<TempPath>) -> Vec<TempPath> {
+pub fn collect_cleanup(mut existing: Vec<AuthGuard>, mut extra: Vec<AuthGuard>) -> Vec<AuthGuard> {
     existing.append(&mut extra);
     existing
 }
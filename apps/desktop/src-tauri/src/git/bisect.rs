@@ -0,0 +1,264 @@
+use crate::{
+    bisect_runner,
+    db::Database,
+    git::{
+        models::{
+            GitBisectMarkRequest, GitBisectRunRequest, GitBisectStartRequest, GitBisectStatus,
+            GitCommandHandle, GitError, GitErrorKind, GitErrorResponse, GitStreamRequest,
+        },
+        operations::run_git_capture,
+        service::GitService,
+        streaming::spawn_streaming_command,
+        util,
+    },
+};
+use std::collections::HashMap;
+use tauri::{AppHandle, State};
+
+async fn bisect_status(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+) -> Result<GitBisectStatus, GitErrorResponse> {
+    let log = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec!["bisect".into(), "log".into()],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !log.success {
+        return Ok(GitBisectStatus::default());
+    }
+
+    let mut status = util::parse_bisect_log(&log.stdout);
+
+    let head = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec!["log".into(), "-1".into(), "--pretty=format:%H|%s".into()],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if let Some((hash, subject)) = head.stdout.split_once('|') {
+        status.current = Some(hash.to_string());
+        status.current_subject = Some(subject.to_string());
+    }
+
+    if status.first_bad_commit.is_none() && !status.good.is_empty() {
+        if let Some(bad) = status.bad.clone() {
+            let mut args = vec![
+                "rev-list".into(),
+                "--bisect-vars".into(),
+                bad,
+                "--not".into(),
+            ];
+            args.extend(status.good.iter().cloned());
+            let vars = run_git_capture(app, service, repository_path, args, None)
+                .await
+                .map_err(GitErrorResponse::from)?;
+            if let Some((remaining, steps)) = util::parse_bisect_vars(&vars.stdout) {
+                status.remaining_revisions = Some(remaining);
+                status.remaining_steps = Some(steps);
+            }
+        }
+    }
+
+    Ok(status)
+}
+
+#[tauri::command]
+pub async fn git_bisect_start(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitBisectStartRequest,
+) -> Result<GitBisectStatus, GitErrorResponse> {
+    let bad = util::sanitize_revision(&request.bad, "bad").map_err(GitErrorResponse::from)?;
+    let good = util::sanitize_revision(&request.good, "good").map_err(GitErrorResponse::from)?;
+
+    let outcome = run_git_capture(
+        &app,
+        &service,
+        &request.repository_path,
+        vec!["bisect".into(), "start".into(), bad, good],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to start bisect",
+        ));
+    }
+
+    bisect_status(&app, &service, &request.repository_path).await
+}
+
+#[tauri::command]
+pub async fn git_bisect_mark(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitBisectMarkRequest,
+) -> Result<GitBisectStatus, GitErrorResponse> {
+    let mut args = vec!["bisect".into(), request.verdict.as_arg().into()];
+    if let Some(commit) = request.commit.as_ref() {
+        args.push(util::sanitize_revision(commit, "commit").map_err(GitErrorResponse::from)?);
+    }
+
+    let outcome = run_git_capture(&app, &service, &request.repository_path, args, None)
+        .await
+        .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to mark bisect commit",
+        ));
+    }
+
+    bisect_status(&app, &service, &request.repository_path).await
+}
+
+#[tauri::command]
+pub async fn git_bisect_status(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    repository_path: String,
+) -> Result<GitBisectStatus, GitErrorResponse> {
+    bisect_status(&app, &service, &repository_path).await
+}
+
+#[tauri::command]
+pub async fn git_bisect_reset(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    repository_path: String,
+) -> Result<GitBisectStatus, GitErrorResponse> {
+    let outcome = run_git_capture(
+        &app,
+        &service,
+        &repository_path,
+        vec!["bisect".into(), "reset".into()],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to reset bisect",
+        ));
+    }
+
+    Ok(GitBisectStatus::default())
+}
+
+struct BisectTestCommand {
+    command: String,
+    args: Vec<String>,
+    env: HashMap<String, String>,
+    cwd: Option<String>,
+}
+
+/// `command, args, env, cwd` of a `runs` row; args and env are stored as JSON.
+type RunRow = (String, Option<String>, Option<String>, Option<String>);
+
+async fn load_run(database: &Database, run_id: &str) -> Result<BisectTestCommand, GitError> {
+    let row: Option<RunRow> =
+        sqlx::query_as("SELECT command, args, env, cwd FROM runs WHERE id = ?")
+            .bind(run_id)
+            .fetch_optional(database.pool())
+            .await?;
+    let (command, args, env, cwd) = row.ok_or_else(|| {
+        GitError::InvalidArgument(format!("run configuration `{run_id}` does not exist"))
+    })?;
+
+    let args = match args {
+        Some(json) => serde_json::from_str(&json).map_err(|e| GitError::Parse(e.to_string()))?,
+        None => Vec::new(),
+    };
+    let env = match env {
+        Some(json) => serde_json::from_str(&json).map_err(|e| GitError::Parse(e.to_string()))?,
+        None => HashMap::new(),
+    };
+
+    Ok(BisectTestCommand {
+        command,
+        args,
+        env,
+        cwd,
+    })
+}
+
+/// Starts `git bisect run` with a saved run configuration or an inline
+/// command; each step's output is streamed as `git://stream` events.
+#[tauri::command]
+pub async fn git_bisect_run(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    database: State<'_, Database>,
+    request: GitBisectRunRequest,
+) -> Result<GitCommandHandle, GitErrorResponse> {
+    let status = bisect_status(&app, &service, &request.repository_path).await?;
+    if status.bad.is_none() || status.good.is_empty() {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            "mark a good and a bad commit before running an automated bisect",
+        ));
+    }
+
+    let test = match (request.run_id.as_deref(), request.command) {
+        (Some(run_id), None) => load_run(&database, run_id)
+            .await
+            .map_err(GitErrorResponse::from)?,
+        (None, Some(command)) => BisectTestCommand {
+            command,
+            args: request.args,
+            env: request.env,
+            cwd: request.cwd,
+        },
+        _ => {
+            return Err(GitErrorResponse::new(
+                GitErrorKind::InvalidArgument,
+                "provide either a run configuration or a command",
+            ))
+        }
+    };
+
+    let mut args = vec!["bisect".into(), "run".into()];
+    if let Some(cwd) = test.cwd.as_ref().filter(|cwd| !cwd.trim().is_empty()) {
+        // `git bisect run` starts the command from the repository root; the
+        // app re-invokes itself to run it from `cwd` instead.
+        let executable =
+            std::env::current_exe().map_err(|err| GitErrorResponse::from(GitError::Io(err)))?;
+        args.extend([
+            executable.to_string_lossy().to_string(),
+            bisect_runner::EXEC_ARG.into(),
+            util::sanitize_arg(cwd, "cwd").map_err(GitErrorResponse::from)?,
+        ]);
+    }
+    args.push(util::sanitize_arg(&test.command, "command").map_err(GitErrorResponse::from)?);
+    for arg in test.args.iter() {
+        if arg.contains('\0') {
+            return Err(GitErrorResponse::from(GitError::InvalidArgument(
+                "arguments may not contain null bytes".into(),
+            )));
+        }
+        args.push(arg.clone());
+    }
+
+    let stream = GitStreamRequest {
+        repository_path: request.repository_path,
+        remote: None,
+        branch: None,
+        auth: None,
+        command_id: request.command_id,
+    };
+    let (handle, _completion) =
+        spawn_streaming_command(app, service, stream, args, test.env).await?;
+    Ok(handle)
+}
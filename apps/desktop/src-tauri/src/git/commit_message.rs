@@ -0,0 +1,356 @@
+//! Commit message templates, conventional-commit linting and suggestions
+//! drawn from history.
+
+use crate::{
+    db::Database,
+    git::{
+        models::{
+            GitCommitLintConfig, GitCommitLintConfigRequest, GitCommitLintRequest,
+            GitCommitLintResult, GitCommitLintSeverity, GitCommitNameCount, GitCommitSuggestions,
+            GitCommitTemplate, GitError, GitErrorKind, GitErrorResponse,
+        },
+        operations::{repository_root, run_git_capture},
+        service::GitService,
+        util,
+    },
+};
+use log::error;
+use std::{collections::HashMap, fs, path::PathBuf};
+use tauri::{AppHandle, State};
+
+/// Commits read when building suggestions.
+const SUGGESTION_HISTORY: u32 = 500;
+const RECENT_MESSAGE_LIMIT: usize = 20;
+
+/// Key under which a repository's lint configuration is stored: its root,
+/// so every subdirectory shares one configuration.
+async fn config_key(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+) -> Result<String, GitErrorResponse> {
+    let root = repository_root(app, service, repository_path).await?;
+    Ok(root.to_string_lossy().to_string())
+}
+
+async fn lint_config(database: &Database, key: &str) -> Result<GitCommitLintConfig, GitError> {
+    let row: Option<(String,)> =
+        sqlx::query_as("SELECT config FROM git_commit_lint_config WHERE repository_path = ?")
+            .bind(key)
+            .fetch_optional(database.pool())
+            .await?;
+    match row {
+        Some((json,)) => serde_json::from_str(&json).map_err(|e| GitError::Parse(e.to_string())),
+        None => Ok(GitCommitLintConfig::default()),
+    }
+}
+
+/// Contents of the file `commit.template` points at, if one is configured.
+pub(crate) async fn commit_template(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+) -> Result<Option<GitCommitTemplate>, GitErrorResponse> {
+    // Exits with 1 when the key is not set; `--path` expands `~`.
+    let outcome = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec![
+            "config".into(),
+            "--path".into(),
+            "--get".into(),
+            "commit.template".into(),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !outcome.success || outcome.stdout.is_empty() {
+        return Ok(None);
+    }
+
+    let mut path = PathBuf::from(&outcome.stdout);
+    if path.is_relative() {
+        path = repository_root(app, service, repository_path)
+            .await?
+            .join(path);
+    }
+    let content = fs::read_to_string(&path).map_err(|err| {
+        GitErrorResponse::new(
+            GitErrorKind::InvalidPath,
+            format!("failed to read commit template {}: {err}", path.display()),
+        )
+    })?;
+    Ok(Some(GitCommitTemplate {
+        path: path.to_string_lossy().to_string(),
+        content,
+    }))
+}
+
+/// A message as `git_commit` would commit it.
+pub(crate) struct CleanedMessage {
+    pub text: String,
+    /// The message is the configured template with nothing filled in.
+    pub unchanged_template: bool,
+}
+
+/// Applies the cleanup `git_commit` does before committing. Messages started
+/// from `commit.template` still carry its comment lines; those get the
+/// cleanup git applies after the editor. Anything else is kept as written,
+/// `#` lines included.
+pub(crate) async fn cleaned_message(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    message: &str,
+) -> CleanedMessage {
+    let mut cleaned = CleanedMessage {
+        text: message.to_string(),
+        unchanged_template: false,
+    };
+    let Ok(Some(template)) = commit_template(app, service, repository_path).await else {
+        return cleaned;
+    };
+    let comments: Vec<&str> = template
+        .content
+        .lines()
+        .filter(|line| line.starts_with('#'))
+        .collect();
+    if !comments.is_empty()
+        && comments
+            .iter()
+            .all(|comment| message.lines().any(|line| line == *comment))
+    {
+        cleaned.text = util::strip_commit_message(message);
+    }
+    let body = util::strip_commit_message(&template.content);
+    cleaned.unchanged_template = !body.is_empty() && cleaned.text.trim() == body;
+    cleaned
+}
+
+/// Lints `message`, cleaned up as `git_commit` would, with the repository's
+/// configuration. When linting is disabled the message is still parsed but
+/// no warnings are reported.
+pub(crate) async fn lint_message(
+    app: &AppHandle,
+    service: &GitService,
+    database: &Database,
+    repository_path: &str,
+    message: &str,
+) -> Result<GitCommitLintResult, GitErrorResponse> {
+    let key = config_key(app, service, repository_path).await?;
+    let config = lint_config(database, &key)
+        .await
+        .map_err(GitErrorResponse::from)?;
+    let message = cleaned_message(app, service, repository_path, message)
+        .await
+        .text;
+
+    if !config.enabled {
+        return Ok(GitCommitLintResult {
+            valid: true,
+            enabled: false,
+            parsed: util::parse_conventional_commit(&message),
+            warnings: Vec::new(),
+        });
+    }
+    Ok(lint_with(&message, &config))
+}
+
+/// Lint rules that apply to a commit about to be made, or `None` when
+/// linting is off for the repository. A configuration that cannot be read
+/// is logged and treated as off so it never blocks the commit.
+pub(crate) async fn commit_lint_config(
+    app: &AppHandle,
+    service: &GitService,
+    database: &Database,
+    repository_path: &str,
+) -> Option<GitCommitLintConfig> {
+    // Linting is off by default; skip resolving the repository root when no
+    // repository has turned it on.
+    let enabled: Result<Option<(i64,)>, _> = sqlx::query_as(
+        "SELECT 1 FROM git_commit_lint_config WHERE json_extract(config, '$.enabled') LIMIT 1",
+    )
+    .fetch_optional(database.pool())
+    .await;
+    if matches!(enabled, Ok(None)) {
+        return None;
+    }
+    let config = match config_key(app, service, repository_path).await {
+        Ok(key) => lint_config(database, &key)
+            .await
+            .map_err(GitErrorResponse::from),
+        Err(err) => Err(err),
+    };
+    match config {
+        Ok(config) => Some(config).filter(|config| config.enabled),
+        Err(err) => {
+            error!(
+                "git:commit failed to read the lint configuration: {}",
+                err.message
+            );
+            None
+        }
+    }
+}
+
+/// Lints `message` as given against enabled rules.
+pub(crate) fn lint_with(message: &str, config: &GitCommitLintConfig) -> GitCommitLintResult {
+    let (parsed, warnings) = util::lint_commit_message(message, config);
+    GitCommitLintResult {
+        valid: !warnings
+            .iter()
+            .any(|warning| warning.severity == GitCommitLintSeverity::Error),
+        enabled: true,
+        parsed,
+        warnings,
+    }
+}
+
+#[tauri::command]
+pub async fn git_commit_template(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    repository_path: String,
+) -> Result<Option<GitCommitTemplate>, GitErrorResponse> {
+    commit_template(&app, &service, &repository_path).await
+}
+
+#[tauri::command]
+pub async fn git_commit_lint_config(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    database: State<'_, Database>,
+    repository_path: String,
+) -> Result<GitCommitLintConfig, GitErrorResponse> {
+    let key = config_key(&app, &service, &repository_path).await?;
+    lint_config(&database, &key)
+        .await
+        .map_err(GitErrorResponse::from)
+}
+
+#[tauri::command]
+pub async fn git_commit_lint_config_set(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    database: State<'_, Database>,
+    request: GitCommitLintConfigRequest,
+) -> Result<GitCommitLintConfig, GitErrorResponse> {
+    let mut config = request.config;
+    for list in [&mut config.types, &mut config.scopes] {
+        let mut seen = Vec::new();
+        for value in list.drain(..) {
+            let value = value.trim().to_string();
+            if !value.is_empty() && !seen.contains(&value) {
+                seen.push(value);
+            }
+        }
+        *list = seen;
+    }
+
+    let key = config_key(&app, &service, &request.repository_path).await?;
+    let json = serde_json::to_string(&config)
+        .map_err(|e| GitErrorResponse::from(GitError::Parse(e.to_string())))?;
+    sqlx::query(
+        "INSERT INTO git_commit_lint_config (repository_path, config, updated_at)
+         VALUES (?, ?, ?)
+         ON CONFLICT(repository_path) DO UPDATE SET
+           config = excluded.config,
+           updated_at = excluded.updated_at",
+    )
+    .bind(&key)
+    .bind(json)
+    .bind(util::now_millis())
+    .execute(database.pool())
+    .await
+    .map_err(|err| GitErrorResponse::from(GitError::from(err)))?;
+    Ok(config)
+}
+
+/// Checks a message against the repository's conventional-commit rules
+/// without committing. `git_commit` refuses messages with errors.
+#[tauri::command]
+pub async fn git_commit_lint(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    database: State<'_, Database>,
+    request: GitCommitLintRequest,
+) -> Result<GitCommitLintResult, GitErrorResponse> {
+    lint_message(
+        &app,
+        &service,
+        &database,
+        &request.repository_path,
+        &request.message,
+    )
+    .await
+}
+
+fn ranked(counts: HashMap<String, u32>) -> Vec<GitCommitNameCount> {
+    let mut ranked: Vec<GitCommitNameCount> = counts
+        .into_iter()
+        .map(|(name, count)| GitCommitNameCount { name, count })
+        .collect();
+    ranked.sort_by(|a, b| b.count.cmp(&a.count).then(a.name.cmp(&b.name)));
+    ranked
+}
+
+/// Types and scopes used in recent conventional commits, and recent subjects
+/// to reuse.
+#[tauri::command]
+pub async fn git_commit_suggestions(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    repository_path: String,
+) -> Result<GitCommitSuggestions, GitErrorResponse> {
+    let outcome = run_git_capture(
+        &app,
+        &service,
+        &repository_path,
+        vec![
+            "log".into(),
+            "--no-merges".into(),
+            "-n".into(),
+            SUGGESTION_HISTORY.to_string(),
+            "--format=%s".into(),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        // An unborn branch has no history to suggest from.
+        return Ok(GitCommitSuggestions {
+            types: Vec::new(),
+            scopes: Vec::new(),
+            recent_messages: Vec::new(),
+        });
+    }
+
+    let mut types: HashMap<String, u32> = HashMap::new();
+    let mut scopes: HashMap<String, u32> = HashMap::new();
+    let mut recent_messages: Vec<String> = Vec::new();
+    for subject in outcome.stdout.lines().map(str::trim) {
+        if subject.is_empty() {
+            continue;
+        }
+        if let Some(parsed) = util::parse_conventional_commit(subject) {
+            *types.entry(parsed.kind.to_ascii_lowercase()).or_default() += 1;
+            if let Some(scope) = parsed.scope {
+                *scopes.entry(scope).or_default() += 1;
+            }
+        }
+        if recent_messages.len() < RECENT_MESSAGE_LIMIT
+            && !recent_messages.iter().any(|message| message == subject)
+        {
+            recent_messages.push(subject.to_string());
+        }
+    }
+
+    Ok(GitCommitSuggestions {
+        types: ranked(types),
+        scopes: ranked(scopes),
+        recent_messages,
+    })
+}
@@ -0,0 +1,236 @@
+use crate::git::{
+    models::{
+        GitCompareFileRequest, GitCompareRequest, GitCompareResponse, GitErrorKind,
+        GitErrorResponse, GitFileDiff, GitRangeDiffPair, GitRangeDiffRequest,
+    },
+    operations::{graph_between, run_git_capture},
+    service::GitService,
+    util,
+};
+use tauri::{AppHandle, State};
+
+async fn resolve_commit(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    revision: &str,
+) -> Result<String, GitErrorResponse> {
+    let outcome = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec![
+            "rev-parse".into(),
+            "--verify".into(),
+            "--quiet".into(),
+            format!("{revision}^{{commit}}"),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !outcome.success || outcome.stdout.is_empty() {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::RefNotFound,
+            format!("`{revision}` does not name a commit"),
+        ));
+    }
+    Ok(outcome.stdout)
+}
+
+/// Range argument diffing `head` against `base`, or against their merge
+/// base by default.
+fn diff_range(base_oid: &str, head_oid: &str, merge_base: Option<bool>) -> String {
+    if merge_base.unwrap_or(true) {
+        format!("{base_oid}...{head_oid}")
+    } else {
+        format!("{base_oid}..{head_oid}")
+    }
+}
+
+fn repository_relative_path(path: &str) -> Result<String, GitErrorResponse> {
+    let path = util::sanitize_arg(path.trim(), "path").map_err(GitErrorResponse::from)?;
+    if path.starts_with('-') {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            format!("path may not start with `-`, got `{path}`"),
+        ));
+    }
+    Ok(path)
+}
+
+/// Commits between two revisions in both directions and the files that
+/// differ, with line counts.
+#[tauri::command]
+pub async fn git_compare(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitCompareRequest,
+) -> Result<GitCompareResponse, GitErrorResponse> {
+    let base = util::sanitize_revision(&request.base, "base").map_err(GitErrorResponse::from)?;
+    let head = util::sanitize_revision(&request.head, "head").map_err(GitErrorResponse::from)?;
+    let base_oid = resolve_commit(&app, &service, &request.repository_path, &base).await?;
+    let head_oid = resolve_commit(&app, &service, &request.repository_path, &head).await?;
+
+    // Exits with 1 when the histories are unrelated.
+    let merge_base = run_git_capture(
+        &app,
+        &service,
+        &request.repository_path,
+        vec!["merge-base".into(), base_oid.clone(), head_oid.clone()],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    let merge_base_oid =
+        Some(merge_base.stdout).filter(|oid| merge_base.success && !oid.is_empty());
+
+    let use_merge_base = request.merge_base.unwrap_or(true);
+    if use_merge_base && merge_base_oid.is_none() {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            format!("`{base}` and `{head}` have no common history"),
+        ));
+    }
+
+    let ahead = graph_between(
+        &app,
+        &service,
+        &request.repository_path,
+        &format!("{base_oid}..{head_oid}"),
+    )
+    .await?;
+    let behind = graph_between(
+        &app,
+        &service,
+        &request.repository_path,
+        &format!("{head_oid}..{base_oid}"),
+    )
+    .await?;
+
+    let stats = run_git_capture(
+        &app,
+        &service,
+        &request.repository_path,
+        vec![
+            "diff".into(),
+            "--numstat".into(),
+            "--summary".into(),
+            "-M".into(),
+            "--no-color".into(),
+            diff_range(&base_oid, &head_oid, request.merge_base),
+            "--".into(),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !stats.success {
+        return Err(GitErrorResponse::from_outcome(
+            &stats,
+            "failed to compare revisions",
+        ));
+    }
+    let files = util::parse_diff_stats(&stats.stdout);
+
+    Ok(GitCompareResponse {
+        additions: files
+            .iter()
+            .map(|file| u64::from(file.additions.unwrap_or(0)))
+            .sum(),
+        deletions: files
+            .iter()
+            .map(|file| u64::from(file.deletions.unwrap_or(0)))
+            .sum(),
+        base_oid,
+        head_oid,
+        merge_base_oid,
+        ahead,
+        behind,
+        files,
+    })
+}
+
+/// Unified diff of one file between two revisions.
+#[tauri::command]
+pub async fn git_compare_file(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitCompareFileRequest,
+) -> Result<GitFileDiff, GitErrorResponse> {
+    let base = util::sanitize_revision(&request.base, "base").map_err(GitErrorResponse::from)?;
+    let head = util::sanitize_revision(&request.head, "head").map_err(GitErrorResponse::from)?;
+    let base_oid = resolve_commit(&app, &service, &request.repository_path, &base).await?;
+    let head_oid = resolve_commit(&app, &service, &request.repository_path, &head).await?;
+    let path = repository_relative_path(&request.path)?;
+
+    let mut args = vec![
+        "diff".into(),
+        "-M".into(),
+        "--no-color".into(),
+        "--no-ext-diff".into(),
+        diff_range(&base_oid, &head_oid, request.merge_base),
+        "--".into(),
+    ];
+    if let Some(original) = request.original_path.as_deref() {
+        args.push(repository_relative_path(original)?);
+    }
+    args.push(path.clone());
+
+    let outcome = run_git_capture(&app, &service, &request.repository_path, args, None)
+        .await
+        .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to diff file",
+        ));
+    }
+
+    Ok(GitFileDiff {
+        path,
+        binary: outcome
+            .stdout
+            .lines()
+            .any(|line| line.starts_with("Binary files ")),
+        patch: outcome.stdout,
+    })
+}
+
+/// Matches the commits of two versions of a branch, typically before and
+/// after a rebase, and shows how each patch changed.
+#[tauri::command]
+pub async fn git_range_diff(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitRangeDiffRequest,
+) -> Result<Vec<GitRangeDiffPair>, GitErrorResponse> {
+    let mut args = vec!["range-diff".into(), "--no-color".into()];
+    if let Some(factor) = request.creation_factor {
+        args.push(format!("--creation-factor={factor}"));
+    }
+    for (range, field) in [
+        (&request.old_range, "old range"),
+        (&request.new_range, "new range"),
+    ] {
+        let range = util::sanitize_revision(range, field).map_err(GitErrorResponse::from)?;
+        if !range.contains("..") {
+            return Err(GitErrorResponse::new(
+                GitErrorKind::InvalidArgument,
+                format!("{field} must look like `base..tip`, got `{range}`"),
+            ));
+        }
+        args.push(range);
+    }
+
+    let outcome = run_git_capture(&app, &service, &request.repository_path, args, None)
+        .await
+        .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to compare ranges",
+        ));
+    }
+    Ok(util::parse_range_diff(&outcome.stdout))
+}
@@ -0,0 +1,307 @@
+use crate::{
+    db::Database,
+    git::{
+        models::{
+            GitCommonConfig, GitCommonConfigRequest, GitConfigEntry, GitConfigScope,
+            GitConfigSetRequest, GitConfigUnsetRequest, GitConfigValue, GitError, GitErrorKind,
+            GitErrorResponse, GitPullRebaseMode,
+        },
+        operations::run_git_capture,
+        service::GitService,
+        util,
+    },
+    git_args,
+};
+use log::error;
+use tauri::{AppHandle, State};
+
+/// Exit code of `git config --unset` when the key does not exist.
+const CONFIG_KEY_MISSING: i32 = 5;
+
+const USER_NAME: &str = "user.name";
+const USER_EMAIL: &str = "user.email";
+const DEFAULT_BRANCH: &str = "init.defaultbranch";
+const PULL_REBASE: &str = "pull.rebase";
+const SIGNING_KEY: &str = "user.signingkey";
+
+/// `settings` column mirroring a global config key.
+fn settings_column(key: &str) -> Option<&'static str> {
+    match key.to_ascii_lowercase().as_str() {
+        USER_NAME => Some("git_user_name"),
+        USER_EMAIL => Some("git_user_email"),
+        DEFAULT_BRANCH => Some("git_default_branch"),
+        PULL_REBASE => Some("git_pull_rebase"),
+        SIGNING_KEY => Some("git_signing_key"),
+        _ => None,
+    }
+}
+
+/// Mirrors a global value the app edits into the `settings` row so the
+/// front-end can show it without spawning git.
+async fn reflect_setting(database: &Database, key: &str, value: Option<&str>) {
+    let Some(column) = settings_column(key) else {
+        return;
+    };
+    let result = sqlx::query(&format!(
+        "INSERT INTO settings (id, {column}) VALUES ('global', ?)
+         ON CONFLICT(id) DO UPDATE SET {column} = excluded.{column}"
+    ))
+    .bind(value)
+    .execute(database.pool())
+    .await;
+    if let Err(err) = result {
+        error!("git:config failed to store {key} in settings: {err}");
+    }
+}
+
+fn scope_flag(scope: GitConfigScope) -> Result<&'static str, GitErrorResponse> {
+    scope.as_flag().ok_or_else(|| {
+        GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            "command line configuration cannot be changed",
+        )
+    })
+}
+
+async fn config_entries(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+) -> Result<Vec<GitConfigEntry>, GitErrorResponse> {
+    let outcome = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec![
+            "config".into(),
+            "--list".into(),
+            "--show-scope".into(),
+            "--show-origin".into(),
+            "-z".into(),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to read git config",
+        ));
+    }
+    Ok(util::parse_config_list(&outcome.stdout))
+}
+
+async fn set_value(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    scope: GitConfigScope,
+    key: &str,
+    value: &str,
+) -> Result<(), GitErrorResponse> {
+    git_args::check_config_value(value)
+        .map_err(|reason| GitErrorResponse::from(GitError::InvalidArgument(reason)))?;
+    let outcome = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec![
+            "config".into(),
+            scope_flag(scope)?.into(),
+            "--replace-all".into(),
+            key.into(),
+            value.into(),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            &format!("failed to set {key}"),
+        ));
+    }
+    Ok(())
+}
+
+async fn unset_value(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    scope: GitConfigScope,
+    key: &str,
+) -> Result<(), GitErrorResponse> {
+    let outcome = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec![
+            "config".into(),
+            scope_flag(scope)?.into(),
+            "--unset-all".into(),
+            key.into(),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !outcome.success && outcome.exit_code != Some(CONFIG_KEY_MISSING) {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            &format!("failed to unset {key}"),
+        ));
+    }
+    Ok(())
+}
+
+/// Every config entry visible from the repository, lowest precedence first,
+/// with the scope and file it comes from.
+#[tauri::command]
+pub async fn git_config_list(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    repository_path: String,
+) -> Result<Vec<GitConfigEntry>, GitErrorResponse> {
+    config_entries(&app, &service, &repository_path).await
+}
+
+/// Validates a key coming from the config editor, which may not touch keys
+/// that make git run programs.
+fn editable_key(key: &str) -> Result<String, GitErrorResponse> {
+    let key = util::sanitize_config_key(key).map_err(GitErrorResponse::from)?;
+    git_args::check_config_key(&key)
+        .map_err(|reason| GitErrorResponse::from(GitError::InvalidArgument(reason)))?;
+    Ok(key)
+}
+
+#[tauri::command]
+pub async fn git_config_set(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    database: State<'_, Database>,
+    request: GitConfigSetRequest,
+) -> Result<(), GitErrorResponse> {
+    let key = editable_key(&request.key)?;
+    set_value(
+        &app,
+        &service,
+        &request.repository_path,
+        request.scope,
+        &key,
+        &request.value,
+    )
+    .await?;
+    if request.scope == GitConfigScope::Global {
+        reflect_setting(&database, &key, Some(&request.value)).await;
+    }
+    Ok(())
+}
+
+/// Removes every value of `key` at `scope`; missing keys are not an error.
+#[tauri::command]
+pub async fn git_config_unset(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    database: State<'_, Database>,
+    request: GitConfigUnsetRequest,
+) -> Result<(), GitErrorResponse> {
+    let key = editable_key(&request.key)?;
+    unset_value(
+        &app,
+        &service,
+        &request.repository_path,
+        request.scope,
+        &key,
+    )
+    .await?;
+    if request.scope == GitConfigScope::Global {
+        reflect_setting(&database, &key, None).await;
+    }
+    Ok(())
+}
+
+fn effective<'a>(entries: &'a [GitConfigEntry], key: &str) -> Option<&'a GitConfigEntry> {
+    entries.iter().rev().find(|entry| entry.key == key)
+}
+
+fn string_value(entries: &[GitConfigEntry], key: &str) -> Option<GitConfigValue<String>> {
+    effective(entries, key).map(|entry| GitConfigValue {
+        value: entry.value.clone(),
+        scope: entry.scope,
+    })
+}
+
+#[tauri::command]
+pub async fn git_config_common(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    repository_path: String,
+) -> Result<GitCommonConfig, GitErrorResponse> {
+    let entries = config_entries(&app, &service, &repository_path).await?;
+    Ok(GitCommonConfig {
+        user_name: string_value(&entries, USER_NAME),
+        user_email: string_value(&entries, USER_EMAIL),
+        default_branch: string_value(&entries, DEFAULT_BRANCH),
+        pull_rebase: effective(&entries, PULL_REBASE).and_then(|entry| {
+            GitPullRebaseMode::parse(&entry.value).map(|value| GitConfigValue {
+                value,
+                scope: entry.scope,
+            })
+        }),
+        signing_key: string_value(&entries, SIGNING_KEY),
+    })
+}
+
+/// Updates identity, default branch, pull and signing settings at one scope.
+#[tauri::command]
+pub async fn git_config_common_set(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    database: State<'_, Database>,
+    request: GitCommonConfigRequest,
+) -> Result<GitCommonConfig, GitErrorResponse> {
+    if let Some(branch) = request.default_branch.as_deref().map(str::trim) {
+        if !branch.is_empty() {
+            util::sanitize_revision(branch, "default branch").map_err(GitErrorResponse::from)?;
+        }
+    }
+
+    let pull_rebase = match (request.pull_rebase, request.unset_pull_rebase) {
+        (Some(mode), _) => Some(mode.as_str().to_string()),
+        (None, true) => Some(String::new()),
+        (None, false) => None,
+    };
+    let changes = [
+        (USER_NAME, request.user_name),
+        (USER_EMAIL, request.user_email),
+        (DEFAULT_BRANCH, request.default_branch),
+        (PULL_REBASE, pull_rebase),
+        (SIGNING_KEY, request.signing_key),
+    ];
+
+    for (key, value) in changes.iter() {
+        let Some(value) = value.as_deref().map(str::trim) else {
+            continue;
+        };
+        if value.is_empty() {
+            unset_value(&app, &service, &request.repository_path, request.scope, key).await?;
+        } else {
+            set_value(
+                &app,
+                &service,
+                &request.repository_path,
+                request.scope,
+                key,
+                value,
+            )
+            .await?;
+        }
+        if request.scope == GitConfigScope::Global {
+            reflect_setting(&database, key, Some(value).filter(|v| !v.is_empty())).await;
+        }
+    }
+
+    git_config_common(app, service, request.repository_path).await
+}
@@ -0,0 +1,258 @@
+use crate::{
+    credential_helper::{self, attribute, format_attributes, parse_attributes},
+    git::{auth::shell_quote, credentials::CredentialStore, models::GitError, util},
+};
+use log::{error, info};
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Write},
+    sync::{Arc, Mutex},
+};
+use uuid::Uuid;
+
+#[cfg(not(unix))]
+use std::net::{TcpListener as Listener, TcpStream as Stream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener as Listener, UnixStream as Stream};
+
+/// Credentials a single git invocation may ask for. The session lives until
+/// that invocation exits and only answers for `hosts`, the remotes the
+/// command was started for, so a hook or other program inheriting the token
+/// cannot read or erase credentials stored for anything else.
+#[derive(Debug, Clone, Default)]
+pub struct HelperSession {
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub passphrase: Option<String>,
+    pub remember: bool,
+    pub hosts: Vec<String>,
+}
+
+type Sessions = Arc<Mutex<HashMap<String, HelperSession>>>;
+
+/// Unregisters its session when the git command that owns it finishes.
+#[derive(Debug)]
+pub struct SessionGuard {
+    token: String,
+    sessions: Sessions,
+}
+
+impl SessionGuard {
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        if let Ok(mut sessions) = self.sessions.lock() {
+            sessions.remove(&self.token);
+        }
+    }
+}
+
+/// Answers git credential helper and ssh askpass requests over a local socket
+/// (a Unix socket in a private directory, or loopback TCP on Windows). Each
+/// request must carry the token of a live session.
+pub struct CredentialServer {
+    endpoint: Option<String>,
+    sessions: Sessions,
+    _socket_dir: Option<tempfile::TempDir>,
+}
+
+impl CredentialServer {
+    pub fn start(store: CredentialStore) -> Self {
+        let sessions: Sessions = Arc::new(Mutex::new(HashMap::new()));
+        match Self::bind() {
+            Ok((listener, endpoint, socket_dir)) => {
+                let accept_sessions = sessions.clone();
+                std::thread::spawn(move || {
+                    for stream in listener.incoming().flatten() {
+                        let sessions = accept_sessions.clone();
+                        let store = store.clone();
+                        tauri::async_runtime::spawn_blocking(move || {
+                            if let Err(err) = handle_connection(stream, &sessions, &store) {
+                                error!("git:credential-helper request failed: {err}");
+                            }
+                        });
+                    }
+                });
+                info!("git:credential-helper listening");
+                Self {
+                    endpoint: Some(endpoint),
+                    sessions,
+                    _socket_dir: socket_dir,
+                }
+            }
+            Err(err) => {
+                error!("git:credential-helper unavailable: {err}");
+                Self {
+                    endpoint: None,
+                    sessions,
+                    _socket_dir: None,
+                }
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    fn bind() -> Result<(Listener, String, Option<tempfile::TempDir>), GitError> {
+        let dir = tempfile::Builder::new()
+            .prefix("projectlib-git-")
+            .tempdir()?;
+        let path = dir.path().join("credentials.sock");
+        let listener = Listener::bind(&path)?;
+        Ok((listener, path.to_string_lossy().to_string(), Some(dir)))
+    }
+
+    #[cfg(not(unix))]
+    fn bind() -> Result<(Listener, String, Option<tempfile::TempDir>), GitError> {
+        let listener = Listener::bind("127.0.0.1:0")?;
+        let endpoint = listener.local_addr()?.to_string();
+        Ok((listener, endpoint, None))
+    }
+
+    pub fn endpoint(&self) -> Result<&str, GitError> {
+        self.endpoint
+            .as_deref()
+            .ok_or_else(|| GitError::Spawn("credential helper is not running".into()))
+    }
+
+    pub fn open_session(&self, session: HelperSession) -> SessionGuard {
+        let token = Uuid::new_v4().simple().to_string();
+        if let Ok(mut sessions) = self.sessions.lock() {
+            sessions.insert(token.clone(), session);
+        }
+        SessionGuard {
+            token,
+            sessions: self.sessions.clone(),
+        }
+    }
+
+    /// Environment that points git at this helper for one invocation. With
+    /// `exclusive` set, helpers from the user's git config are cleared so the
+    /// session's explicit credentials take precedence.
+    pub fn helper_env(
+        &self,
+        guard: &SessionGuard,
+        exclusive: bool,
+    ) -> Result<HashMap<String, String>, GitError> {
+        let endpoint = self.endpoint()?;
+        let executable = std::env::current_exe()?;
+        let helper = format!(
+            "!{} {}",
+            shell_quote(&executable.to_string_lossy()),
+            credential_helper::HELPER_ARG
+        );
+
+        let mut entries = Vec::new();
+        if exclusive {
+            entries.push(String::new());
+        }
+        entries.push(helper);
+
+        let mut env = HashMap::new();
+        env.insert(credential_helper::ENDPOINT_ENV.into(), endpoint.to_string());
+        env.insert(
+            credential_helper::TOKEN_ENV.into(),
+            guard.token().to_string(),
+        );
+        env.insert("GIT_TERMINAL_PROMPT".into(), "0".into());
+        env.insert("GIT_CONFIG_COUNT".into(), entries.len().to_string());
+        for (idx, value) in entries.into_iter().enumerate() {
+            env.insert(format!("GIT_CONFIG_KEY_{idx}"), "credential.helper".into());
+            env.insert(format!("GIT_CONFIG_VALUE_{idx}"), value);
+        }
+        Ok(env)
+    }
+}
+
+fn handle_connection(
+    stream: Stream,
+    sessions: &Sessions,
+    store: &CredentialStore,
+) -> Result<(), GitError> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request = String::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        request.push_str(&line);
+    }
+
+    let attributes = parse_attributes(&request);
+    let session =
+        attribute(&attributes, "token").and_then(|token| sessions.lock().ok()?.get(token).cloned());
+    let Some(session) = session else {
+        return Ok(());
+    };
+
+    let response = respond(&session, &attributes, store)?;
+    let mut stream = stream;
+    stream.write_all(format_attributes(&response).as_bytes())?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn respond(
+    session: &HelperSession,
+    attributes: &[(String, String)],
+    store: &CredentialStore,
+) -> Result<Vec<(String, String)>, GitError> {
+    let host = attribute(attributes, "host")
+        .and_then(util::remote_host)
+        .filter(|host| session.hosts.contains(host));
+    match attribute(attributes, "operation").unwrap_or("") {
+        "get" => {
+            // Explicit credentials were handed over for this command; when
+            // its remote could not be resolved they are still answered.
+            if let (Some(username), Some(password)) = (&session.username, &session.password) {
+                if host.is_none() && !session.hosts.is_empty() {
+                    return Ok(Vec::new());
+                }
+                return Ok(vec![
+                    ("username".into(), username.clone()),
+                    ("password".into(), password.clone()),
+                ]);
+            }
+            let Some(host) = host else {
+                return Ok(Vec::new());
+            };
+            Ok(match store.lookup(&host)? {
+                Some((username, password)) => {
+                    vec![("username".into(), username), ("password".into(), password)]
+                }
+                None => Vec::new(),
+            })
+        }
+        "store" => {
+            if session.remember {
+                if let (Some(host), Some(username), Some(password)) = (
+                    host.as_deref(),
+                    attribute(attributes, "username"),
+                    attribute(attributes, "password"),
+                ) {
+                    store.add(host, username, password)?;
+                }
+            }
+            Ok(Vec::new())
+        }
+        "erase" => {
+            // Only forget stored credentials; explicit ones belong to the caller.
+            if session.password.is_none() {
+                if let Some(host) = host {
+                    store.remove(&host)?;
+                }
+            }
+            Ok(Vec::new())
+        }
+        "askpass" => Ok(session
+            .passphrase
+            .as_ref()
+            .map(|passphrase| vec![("password".into(), passphrase.clone())])
+            .unwrap_or_default()),
+        _ => Ok(Vec::new()),
+    }
+}
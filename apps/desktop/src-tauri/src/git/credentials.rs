@@ -0,0 +1,331 @@
+use crate::git::{
+    models::{
+        GitCredentialBackend, GitCredentialRequest, GitError, GitErrorResponse, GitStoredCredential,
+    },
+    util,
+};
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+use tauri::State;
+
+const KEYRING_SERVICE: &str = "projectlib-git";
+const INDEX_FILE: &str = "git-credentials.json";
+const VAULT_FILE: &str = "git-credentials.vault";
+const VAULT_KEY_FILE: &str = "git-credentials.key";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct CredentialIndexEntry {
+    username: String,
+    backend: GitCredentialBackend,
+    secret_hint: String,
+    updated_at: i64,
+}
+
+/// Stores Git credentials per remote host. Secrets live in the OS keyring when
+/// it is reachable and otherwise in an encrypted vault file; only the
+/// non-secret index (host, username, backend) is kept in plain JSON.
+///
+/// The vault is only a fallback for systems without a keyring, so its key has
+/// nowhere safer to live than a private file next to it. That keeps secrets
+/// out of plain sight but does not protect them from anyone who can read the
+/// app data directory: the vault obfuscates, it does not secure.
+#[derive(Clone)]
+pub struct CredentialStore {
+    dir: PathBuf,
+    lock: Arc<Mutex<()>>,
+}
+
+impl CredentialStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    pub fn list(&self) -> Result<Vec<GitStoredCredential>, GitError> {
+        let _guard = self.lock.lock().unwrap();
+        let index = self.read_index()?;
+        let mut credentials: Vec<GitStoredCredential> = index
+            .into_iter()
+            .map(|(host, entry)| GitStoredCredential {
+                host,
+                username: entry.username,
+                backend: entry.backend,
+                redacted_secret: format!("****{}", entry.secret_hint),
+                updated_at: entry.updated_at,
+            })
+            .collect();
+        credentials.sort_by(|a, b| a.host.cmp(&b.host));
+        Ok(credentials)
+    }
+
+    pub fn add(
+        &self,
+        host: &str,
+        username: &str,
+        secret: &str,
+    ) -> Result<GitStoredCredential, GitError> {
+        let host = normalize_host(host)?;
+        let username = util::sanitize_arg(username.trim(), "username")?;
+        let secret = util::sanitize_arg(secret, "secret")?;
+
+        let _guard = self.lock.lock().unwrap();
+        let mut index = self.read_index()?;
+
+        let backend = match keyring_entry(&host).and_then(|entry| entry.set_password(&secret)) {
+            Ok(()) => {
+                self.vault_remove(&host)?;
+                GitCredentialBackend::Keyring
+            }
+            Err(err) => {
+                info!("git:credentials keyring unavailable for {host}, using vault: {err}");
+                let mut vault = self.read_vault()?;
+                vault.insert(host.clone(), secret.clone());
+                self.write_vault(&vault)?;
+                GitCredentialBackend::Vault
+            }
+        };
+
+        let entry = CredentialIndexEntry {
+            username: username.clone(),
+            backend,
+            secret_hint: secret_hint(&secret),
+            updated_at: util::now_millis(),
+        };
+        index.insert(host.clone(), entry.clone());
+        self.write_index(&index)?;
+
+        Ok(GitStoredCredential {
+            host,
+            username,
+            backend,
+            redacted_secret: format!("****{}", entry.secret_hint),
+            updated_at: entry.updated_at,
+        })
+    }
+
+    pub fn remove(&self, host: &str) -> Result<bool, GitError> {
+        let host = normalize_host(host)?;
+        let _guard = self.lock.lock().unwrap();
+        let mut index = self.read_index()?;
+        let Some(entry) = index.remove(&host) else {
+            return Ok(false);
+        };
+
+        match entry.backend {
+            GitCredentialBackend::Keyring => {
+                match keyring_entry(&host).and_then(|entry| entry.delete_credential()) {
+                    Ok(()) | Err(keyring::Error::NoEntry) => {}
+                    Err(err) => return Err(GitError::Keyring(err.to_string())),
+                }
+            }
+            GitCredentialBackend::Vault => self.vault_remove(&host)?,
+        }
+
+        self.write_index(&index)?;
+        Ok(true)
+    }
+
+    /// Returns the username and secret stored for `host`, if any.
+    pub fn lookup(&self, host: &str) -> Result<Option<(String, String)>, GitError> {
+        let host = normalize_host(host)?;
+        let _guard = self.lock.lock().unwrap();
+        let index = self.read_index()?;
+        let Some(entry) = index.get(&host) else {
+            return Ok(None);
+        };
+
+        let secret = match entry.backend {
+            GitCredentialBackend::Keyring => {
+                match keyring_entry(&host).and_then(|entry| entry.get_password()) {
+                    Ok(secret) => Some(secret),
+                    Err(keyring::Error::NoEntry) => None,
+                    Err(err) => return Err(GitError::Keyring(err.to_string())),
+                }
+            }
+            GitCredentialBackend::Vault => self.read_vault()?.remove(&host),
+        };
+
+        Ok(secret.map(|secret| (entry.username.clone(), secret)))
+    }
+
+    fn read_index(&self) -> Result<HashMap<String, CredentialIndexEntry>, GitError> {
+        let path = self.dir.join(INDEX_FILE);
+        if !path.exists() {
+            return Ok(HashMap::new());
+        }
+        let contents = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    fn write_index(&self, index: &HashMap<String, CredentialIndexEntry>) -> Result<(), GitError> {
+        let contents = serde_json::to_vec_pretty(index)?;
+        write_private(&self.dir.join(INDEX_FILE), &contents)
+    }
+
+    /// Reads the vault key, creating one only while there is no vault yet.
+    /// Replacing the key of an existing vault would make it undecryptable.
+    fn vault_key(&self) -> Result<Key, GitError> {
+        let path = self.dir.join(VAULT_KEY_FILE);
+        match fs::read(&path) {
+            Ok(bytes) if bytes.len() == 32 => return Ok(*Key::from_slice(&bytes)),
+            Ok(_) => {
+                return Err(GitError::Vault(format!(
+                    "{} is damaged; remove the vault and key to start over",
+                    path.display()
+                )))
+            }
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+            Err(_) if self.dir.join(VAULT_FILE).exists() => {
+                return Err(GitError::Vault(format!(
+                    "{} is missing, so the existing vault cannot be decrypted",
+                    path.display()
+                )))
+            }
+            Err(_) => {}
+        }
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        write_private(&path, key.as_slice())?;
+        Ok(key)
+    }
+
+    fn read_vault(&self) -> Result<HashMap<String, String>, GitError> {
+        let path = self.dir.join(VAULT_FILE);
+        if !path.exists() {
+            return Ok(HashMap::new());
+        }
+        let bytes = fs::read(path)?;
+        if bytes.len() < NONCE_LEN {
+            return Err(GitError::Vault("the vault file is truncated".into()));
+        }
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        let cipher = ChaCha20Poly1305::new(&self.vault_key()?);
+        let plain = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| GitError::Vault("the vault could not be decrypted".into()))?;
+        Ok(serde_json::from_slice(&plain)?)
+    }
+
+    fn write_vault(&self, vault: &HashMap<String, String>) -> Result<(), GitError> {
+        let plain = serde_json::to_vec(vault)?;
+        let cipher = ChaCha20Poly1305::new(&self.vault_key()?);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plain.as_ref())
+            .map_err(|_| GitError::Vault("the vault could not be encrypted".into()))?;
+
+        let mut contents = nonce.to_vec();
+        contents.extend_from_slice(&ciphertext);
+        write_private(&self.dir.join(VAULT_FILE), &contents)
+    }
+
+    fn vault_remove(&self, host: &str) -> Result<(), GitError> {
+        let mut vault = self.read_vault()?;
+        if vault.remove(host).is_some() {
+            self.write_vault(&vault)?;
+        }
+        Ok(())
+    }
+}
+
+fn keyring_entry(host: &str) -> Result<keyring::Entry, keyring::Error> {
+    keyring::Entry::new(KEYRING_SERVICE, host)
+}
+
+fn normalize_host(value: &str) -> Result<String, GitError> {
+    util::remote_host(value)
+        .ok_or_else(|| GitError::InvalidArgument(format!("`{value}` is not a valid host")))
+}
+
+fn secret_hint(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.len() < 12 {
+        String::new()
+    } else {
+        chars[chars.len() - 4..].iter().collect()
+    }
+}
+
+/// Writes `contents` readable by the owner only: the data goes to a
+/// temporary file created with mode 0600 that then replaces `path`, so the
+/// file is never world-readable nor left half-written.
+fn write_private(path: &Path, contents: &[u8]) -> Result<(), GitError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp = path.with_file_name(temp_name);
+
+    // A temporary file left by a crash may have other permissions; the mode
+    // only applies to files this call creates.
+    let _ = fs::remove_file(&temp);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let written = options.open(&temp).and_then(|mut file| {
+        file.write_all(contents)?;
+        file.sync_all()
+    });
+    if let Err(err) = written.and_then(|()| fs::rename(&temp, path)) {
+        let _ = fs::remove_file(&temp);
+        return Err(err.into());
+    }
+    Ok(())
+}
+
+async fn run_blocking<T, F>(store: &CredentialStore, task: F) -> Result<T, GitErrorResponse>
+where
+    T: Send + 'static,
+    F: FnOnce(CredentialStore) -> Result<T, GitError> + Send + 'static,
+{
+    let store = store.clone();
+    tauri::async_runtime::spawn_blocking(move || task(store))
+        .await
+        .map_err(|e| GitErrorResponse::from(GitError::Spawn(e.to_string())))?
+        .map_err(GitErrorResponse::from)
+}
+
+#[tauri::command]
+pub async fn git_credentials_list(
+    store: State<'_, CredentialStore>,
+) -> Result<Vec<GitStoredCredential>, GitErrorResponse> {
+    run_blocking(&store, |store| store.list()).await
+}
+
+#[tauri::command]
+pub async fn git_credentials_add(
+    store: State<'_, CredentialStore>,
+    request: GitCredentialRequest,
+) -> Result<GitStoredCredential, GitErrorResponse> {
+    run_blocking(&store, move |store| {
+        store.add(&request.host, &request.username, &request.secret)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn git_credentials_remove(
+    store: State<'_, CredentialStore>,
+    host: String,
+) -> Result<bool, GitErrorResponse> {
+    run_blocking(&store, move |store| store.remove(&host)).await
+}
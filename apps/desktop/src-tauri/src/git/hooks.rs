@@ -0,0 +1,367 @@
+use crate::git::{
+    models::{
+        GitError, GitErrorKind, GitErrorResponse, GitHook, GitHookAction, GitHookContent,
+        GitHookInstallRequest, GitHookInstallResponse, GitHookNotice, GitHookPreviewRequest,
+        GitHookRequest, GitHookToggleRequest, GitHooksResponse,
+    },
+    operations::{repository_root, run_git_capture},
+    service::GitService,
+    util,
+};
+use log::error;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use tauri::{AppHandle, Emitter, State};
+
+pub const HOOKS_EVENT: &str = "git://hooks";
+
+/// Suffix the app appends to switch a hook off without deleting it.
+const DISABLED_SUFFIX: &str = ".disabled";
+const DEFAULT_TEMPLATE_DIR: &str = ".githooks";
+
+/// Client-side hooks git runs; anything else in the hooks directory is
+/// ignored.
+const KNOWN_HOOKS: &[&str] = &[
+    "applypatch-msg",
+    "pre-applypatch",
+    "post-applypatch",
+    "pre-commit",
+    "pre-merge-commit",
+    "prepare-commit-msg",
+    "commit-msg",
+    "post-commit",
+    "pre-rebase",
+    "post-checkout",
+    "post-merge",
+    "pre-push",
+    "reference-transaction",
+    "push-to-checkout",
+    "pre-auto-gc",
+    "post-rewrite",
+    "sendemail-validate",
+    "fsmonitor-watchman",
+    "post-index-change",
+];
+
+fn io_error(err: std::io::Error) -> GitErrorResponse {
+    GitErrorResponse::from(GitError::Io(err))
+}
+
+fn hook_name(name: &str) -> Result<&str, GitErrorResponse> {
+    let name = name.trim();
+    if KNOWN_HOOKS.contains(&name) {
+        Ok(name)
+    } else {
+        Err(GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            format!("`{name}` is not a git hook"),
+        ))
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|metadata| metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+fn make_executable(path: &Path) -> Result<(), GitErrorResponse> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755)).map_err(io_error)?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+/// Directory git reads hooks from, honouring `core.hooksPath`, and whether
+/// that setting is in effect.
+async fn hooks_dir(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+) -> Result<(PathBuf, bool), GitErrorResponse> {
+    let outcome = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec!["rev-parse".into(), "--git-path".into(), "hooks".into()],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to locate the hooks directory",
+        ));
+    }
+    // Relative to the working directory unless git printed an absolute path.
+    let working_dir = util::canonicalize_path(repository_path).map_err(GitErrorResponse::from)?;
+    let dir = working_dir.join(outcome.stdout);
+
+    // Exits with 1 when the key is not set.
+    let custom = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec!["config".into(), "--get".into(), "core.hooksPath".into()],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+
+    Ok((dir, custom.success && !custom.stdout.is_empty()))
+}
+
+fn describe_hook(dir: &Path, name: &str) -> Option<GitHook> {
+    let enabled = dir.join(name);
+    let disabled = dir.join(format!("{name}{DISABLED_SUFFIX}"));
+    let (path, is_enabled) = if enabled.is_file() {
+        (enabled, true)
+    } else if disabled.is_file() {
+        (disabled, false)
+    } else {
+        return None;
+    };
+    Some(GitHook {
+        name: name.to_string(),
+        executable: is_executable(&path),
+        path: path.to_string_lossy().to_string(),
+        enabled: is_enabled,
+    })
+}
+
+fn installed_hooks(dir: &Path) -> Result<Vec<GitHook>, GitErrorResponse> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(io_error(err)),
+    };
+
+    // Keyed by name so a hook present both enabled and disabled is listed
+    // once, as the copy git will run.
+    let mut hooks = BTreeMap::new();
+    for entry in entries {
+        let entry = entry.map_err(io_error)?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let name = file_name
+            .strip_suffix(DISABLED_SUFFIX)
+            .unwrap_or(&file_name);
+        if !KNOWN_HOOKS.contains(&name) || hooks.contains_key(name) {
+            continue;
+        }
+        if let Some(hook) = describe_hook(dir, name) {
+            hooks.insert(name.to_string(), hook);
+        }
+    }
+    Ok(hooks.into_values().collect())
+}
+
+async fn list_hooks(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+) -> Result<GitHooksResponse, GitErrorResponse> {
+    let (dir, custom_hooks_path) = hooks_dir(app, service, repository_path).await?;
+    Ok(GitHooksResponse {
+        hooks: installed_hooks(&dir)?,
+        hooks_path: dir.to_string_lossy().to_string(),
+        custom_hooks_path,
+    })
+}
+
+/// Names of the hooks git will run for `action`, in the order it runs them.
+async fn hooks_for_action(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    action: GitHookAction,
+) -> Result<Vec<String>, GitErrorResponse> {
+    let (dir, _) = hooks_dir(app, service, repository_path).await?;
+    Ok(action
+        .hooks()
+        .iter()
+        .filter_map(|name| describe_hook(&dir, name))
+        .filter(|hook| hook.enabled && hook.executable)
+        .map(|hook| hook.name)
+        .collect())
+}
+
+/// Tells the UI which hooks are about to run before the app starts `action`.
+/// Failures are logged; they never block the action itself.
+pub(crate) async fn announce_hooks(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    action: GitHookAction,
+    command_id: Option<String>,
+) {
+    match hooks_for_action(app, service, repository_path, action).await {
+        Ok(hooks) => {
+            let payload = GitHookNotice {
+                repository_path: repository_path.to_string(),
+                action,
+                command_id,
+                hooks,
+            };
+            let _ = app.emit(HOOKS_EVENT, payload);
+        }
+        Err(err) => error!("git:hooks failed to resolve hooks: {}", err.message),
+    }
+}
+
+/// Hooks installed in the directory git reads them from, including disabled
+/// ones.
+#[tauri::command]
+pub async fn git_hooks_list(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    repository_path: String,
+) -> Result<GitHooksResponse, GitErrorResponse> {
+    list_hooks(&app, &service, &repository_path).await
+}
+
+#[tauri::command]
+pub async fn git_hook_read(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitHookRequest,
+) -> Result<GitHookContent, GitErrorResponse> {
+    let name = hook_name(&request.name)?;
+    let (dir, _) = hooks_dir(&app, &service, &request.repository_path).await?;
+    let hook = describe_hook(&dir, name).ok_or_else(|| {
+        GitErrorResponse::new(
+            GitErrorKind::InvalidPath,
+            format!("hook `{name}` is not installed"),
+        )
+    })?;
+    let content = fs::read(&hook.path).map_err(io_error)?;
+    Ok(GitHookContent {
+        content: String::from_utf8_lossy(&content).to_string(),
+        hook,
+    })
+}
+
+/// Enables or disables a hook by renaming it to or from `<name>.disabled`.
+/// Enabling also marks the script executable.
+#[tauri::command]
+pub async fn git_hook_set_enabled(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitHookToggleRequest,
+) -> Result<GitHooksResponse, GitErrorResponse> {
+    let name = hook_name(&request.name)?;
+    let (dir, _) = hooks_dir(&app, &service, &request.repository_path).await?;
+    let enabled = dir.join(name);
+    let disabled = dir.join(format!("{name}{DISABLED_SUFFIX}"));
+
+    let (from, to) = if request.enabled {
+        (&disabled, &enabled)
+    } else {
+        (&enabled, &disabled)
+    };
+    if from.is_file() {
+        if to.exists() {
+            return Err(GitErrorResponse::new(
+                GitErrorKind::InvalidPath,
+                format!("{} already exists", to.display()),
+            ));
+        }
+        fs::rename(from, to).map_err(io_error)?;
+    } else if !to.is_file() {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidPath,
+            format!("hook `{name}` is not installed"),
+        ));
+    }
+    if request.enabled {
+        make_executable(&enabled)?;
+    }
+
+    list_hooks(&app, &service, &request.repository_path).await
+}
+
+/// Copies hook scripts from a directory committed to the project (by default
+/// `.githooks`) into the hooks directory. Existing hooks are kept unless
+/// `overwrite` is set.
+#[tauri::command]
+pub async fn git_hooks_install_template(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitHookInstallRequest,
+) -> Result<GitHookInstallResponse, GitErrorResponse> {
+    let root = repository_root(&app, &service, &request.repository_path).await?;
+    let template = request
+        .template_dir
+        .as_deref()
+        .map(str::trim)
+        .filter(|dir| !dir.is_empty())
+        .unwrap_or(DEFAULT_TEMPLATE_DIR);
+    let template = util::repository_file(&root, template).map_err(GitErrorResponse::from)?;
+    if !template.is_dir() {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidPath,
+            format!("{} is not a directory", template.display()),
+        ));
+    }
+
+    let (dir, _) = hooks_dir(&app, &service, &request.repository_path).await?;
+    fs::create_dir_all(&dir).map_err(io_error)?;
+    let template = fs::canonicalize(&template).map_err(io_error)?;
+    if fs::canonicalize(&dir).map_err(io_error)? == template {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            "core.hooksPath already points at the template directory",
+        ));
+    }
+
+    let overwrite = request.overwrite.unwrap_or(false);
+    let mut installed = Vec::new();
+    let mut skipped = Vec::new();
+    for name in KNOWN_HOOKS {
+        let source = template.join(name);
+        if !source.is_file() {
+            continue;
+        }
+        let target = dir.join(name);
+        let disabled = dir.join(format!("{name}{DISABLED_SUFFIX}"));
+        if !overwrite && (target.exists() || disabled.exists()) {
+            skipped.push(name.to_string());
+            continue;
+        }
+        if disabled.exists() {
+            fs::remove_file(&disabled).map_err(io_error)?;
+        }
+        fs::copy(&source, &target).map_err(io_error)?;
+        make_executable(&target)?;
+        installed.push(name.to_string());
+    }
+
+    Ok(GitHookInstallResponse {
+        installed,
+        skipped,
+        hooks: list_hooks(&app, &service, &request.repository_path).await?,
+    })
+}
+
+/// Hooks that will run for `action`, as announced on `git://hooks` before
+/// commits and pushes.
+#[tauri::command]
+pub async fn git_hooks_preview(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitHookPreviewRequest,
+) -> Result<Vec<String>, GitErrorResponse> {
+    hooks_for_action(&app, &service, &request.repository_path, request.action).await
+}
@@ -0,0 +1,159 @@
+use crate::git::{
+    models::{
+        GitError, GitErrorKind, GitErrorResponse, GitIgnoreAddRequest, GitIgnoreAddResponse,
+        GitIgnoreMatch, GitIgnoreTarget, GitPathsRequest,
+    },
+    operations::run_git_capture,
+    service::GitService,
+    util,
+};
+use std::{collections::HashSet, fs, path::PathBuf};
+use tauri::{AppHandle, State};
+
+/// Explains for each path whether it is ignored and which pattern decided it.
+#[tauri::command]
+pub async fn git_check_ignore(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitPathsRequest,
+) -> Result<Vec<GitIgnoreMatch>, GitErrorResponse> {
+    if request.paths.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // `-z` needs `--stdin`; keep non-ASCII paths unquoted instead.
+    let mut args = vec![
+        "-c".into(),
+        "core.quotePath=false".into(),
+        "check-ignore".into(),
+        "-v".into(),
+        "-n".into(),
+        "--".into(),
+    ];
+    for path in request.paths.iter() {
+        args.push(util::sanitize_arg(path, "path").map_err(GitErrorResponse::from)?);
+    }
+
+    let outcome = run_git_capture(&app, &service, &request.repository_path, args, None)
+        .await
+        .map_err(GitErrorResponse::from)?;
+    // Exit code 1 only means that none of the paths are ignored.
+    if !outcome.success && outcome.exit_code != Some(1) {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to check ignore rules",
+        ));
+    }
+
+    Ok(util::parse_check_ignore(&outcome.stdout))
+}
+
+async fn ignore_file(
+    app: &AppHandle,
+    service: &GitService,
+    request: &GitIgnoreAddRequest,
+) -> Result<PathBuf, GitErrorResponse> {
+    match request.target {
+        GitIgnoreTarget::Gitignore => {
+            let root = util::canonicalize_path(&request.repository_path)
+                .map_err(GitErrorResponse::from)?;
+            let directory = match request.directory.as_deref().map(str::trim) {
+                Some(directory) if !directory.is_empty() && directory != "." => {
+                    util::repository_file(&root, directory).map_err(GitErrorResponse::from)?
+                }
+                _ => root,
+            };
+            Ok(directory.join(".gitignore"))
+        }
+        GitIgnoreTarget::InfoExclude => {
+            // Resolves to the common git directory for linked worktrees.
+            let outcome = run_git_capture(
+                app,
+                service,
+                &request.repository_path,
+                vec![
+                    "rev-parse".into(),
+                    "--git-path".into(),
+                    "info/exclude".into(),
+                ],
+                None,
+            )
+            .await
+            .map_err(GitErrorResponse::from)?;
+            if !outcome.success {
+                return Err(GitErrorResponse::from_outcome(
+                    &outcome,
+                    "failed to locate info/exclude",
+                ));
+            }
+            // Relative to the working directory unless git printed an
+            // absolute path.
+            let working_dir = util::canonicalize_path(&request.repository_path)
+                .map_err(GitErrorResponse::from)?;
+            Ok(working_dir.join(outcome.stdout))
+        }
+    }
+}
+
+/// Appends patterns to a `.gitignore` or to `.git/info/exclude`, skipping
+/// patterns the file already contains.
+#[tauri::command]
+pub async fn git_ignore_add(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitIgnoreAddRequest,
+) -> Result<GitIgnoreAddResponse, GitErrorResponse> {
+    let mut patterns = Vec::new();
+    for pattern in request.patterns.iter() {
+        let pattern =
+            util::sanitize_arg(pattern.trim(), "pattern").map_err(GitErrorResponse::from)?;
+        if pattern.contains(['\n', '\r']) {
+            return Err(GitErrorResponse::new(
+                GitErrorKind::InvalidArgument,
+                "patterns must be a single line",
+            ));
+        }
+        patterns.push(pattern);
+    }
+    if patterns.is_empty() {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            "no patterns provided",
+        ));
+    }
+
+    let file = ignore_file(&app, &service, &request).await?;
+    let mut content = match fs::read_to_string(&file) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(GitErrorResponse::from(GitError::Io(err))),
+    };
+
+    let mut existing: HashSet<String> = content
+        .lines()
+        .map(|line| line.trim().to_string())
+        .collect();
+    let added: Vec<String> = patterns
+        .into_iter()
+        .filter(|pattern| existing.insert(pattern.clone()))
+        .collect();
+
+    if !added.is_empty() {
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        for pattern in added.iter() {
+            content.push_str(pattern);
+            content.push('\n');
+        }
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent).map_err(|e| GitErrorResponse::from(GitError::Io(e)))?;
+        }
+        fs::write(&file, content).map_err(|e| GitErrorResponse::from(GitError::Io(e)))?;
+    }
+
+    Ok(GitIgnoreAddResponse {
+        file: file.to_string_lossy().to_string(),
+        added,
+    })
+}
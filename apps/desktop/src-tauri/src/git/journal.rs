@@ -0,0 +1,411 @@
+use crate::{
+    db::Database,
+    git::{
+        models::{
+            GitError, GitErrorKind, GitErrorResponse, GitOperationKind, GitOperationRecord,
+            GitReflogEntry, GitReflogRequest, GitResetMode,
+        },
+        operations::run_git_capture,
+        service::GitService,
+        util,
+    },
+};
+use log::{error, info};
+use tauri::{AppHandle, Manager, State};
+use uuid::Uuid;
+
+const DEFAULT_REFLOG_LIMIT: usize = 100;
+const OPERATION_LIST_LIMIT: i64 = 50;
+
+/// Symbolic ref and commit of HEAD at one point in time.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HeadSnapshot {
+    pub symbolic: Option<String>,
+    pub oid: Option<String>,
+}
+
+/// An operation about to be written to the `git_operations` journal.
+#[derive(Debug, Clone)]
+pub(crate) struct OperationEntry {
+    pub kind: GitOperationKind,
+    pub ref_name: Option<String>,
+    pub previous_head: Option<String>,
+    pub previous_oid: Option<String>,
+    pub new_oid: Option<String>,
+    pub description: String,
+    pub reset_mode: Option<GitResetMode>,
+}
+
+/// `id, repository_path, kind, ref_name, previous_head, previous_oid,
+/// new_oid, description, reset_mode, created_at, undone_at` of a
+/// `git_operations` row.
+type OperationRow = (
+    String,
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    String,
+    Option<String>,
+    i64,
+    Option<i64>,
+);
+
+const OPERATION_COLUMNS: &str = "id, repository_path, kind, ref_name, previous_head, previous_oid, new_oid, description, reset_mode, created_at, undone_at";
+
+pub(crate) fn repository_key(repository_path: &str) -> Result<String, GitError> {
+    util::canonicalize_path(repository_path).map(|path| path.to_string_lossy().to_string())
+}
+
+fn record_from_row(row: OperationRow) -> Result<GitOperationRecord, GitError> {
+    let (
+        id,
+        repository_path,
+        kind,
+        ref_name,
+        previous_head,
+        previous_oid,
+        new_oid,
+        description,
+        reset_mode,
+        created_at,
+        undone_at,
+    ) = row;
+    let kind = GitOperationKind::parse(&kind)
+        .ok_or_else(|| GitError::Parse(format!("unknown git operation kind `{kind}`")))?;
+    Ok(GitOperationRecord {
+        id,
+        repository_path,
+        kind,
+        ref_name,
+        previous_head,
+        previous_oid,
+        new_oid,
+        description,
+        reset_mode: reset_mode.as_deref().and_then(GitResetMode::parse),
+        created_at,
+        undone_at,
+    })
+}
+
+pub(crate) async fn resolve_ref(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    rev: &str,
+) -> Option<String> {
+    let outcome = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec![
+            "rev-parse".into(),
+            "-q".into(),
+            "--verify".into(),
+            rev.into(),
+        ],
+        None,
+    )
+    .await
+    .ok()?;
+    (outcome.success && !outcome.stdout.is_empty()).then_some(outcome.stdout)
+}
+
+pub(crate) async fn snapshot_head(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+) -> HeadSnapshot {
+    let symbolic = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec!["symbolic-ref".into(), "-q".into(), "HEAD".into()],
+        None,
+    )
+    .await
+    .ok()
+    .filter(|outcome| outcome.success && !outcome.stdout.is_empty())
+    .map(|outcome| outcome.stdout);
+
+    HeadSnapshot {
+        symbolic,
+        oid: resolve_ref(app, service, repository_path, "HEAD").await,
+    }
+}
+
+/// Journals an operation the app performed. Failures are only logged since
+/// the git command itself has already succeeded.
+pub(crate) async fn record_operation(
+    app: &AppHandle,
+    repository_path: &str,
+    entry: OperationEntry,
+) {
+    let key = match repository_key(repository_path) {
+        Ok(key) => key,
+        Err(err) => {
+            error!("git:journal record failed: {err}");
+            return;
+        }
+    };
+
+    let database = app.state::<Database>();
+    let result = sqlx::query(
+        "INSERT INTO git_operations (id, repository_path, kind, ref_name, previous_head, previous_oid, new_oid, description, reset_mode, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&key)
+    .bind(entry.kind.as_str())
+    .bind(&entry.ref_name)
+    .bind(&entry.previous_head)
+    .bind(&entry.previous_oid)
+    .bind(&entry.new_oid)
+    .bind(&entry.description)
+    .bind(entry.reset_mode.map(|mode| mode.as_str()))
+    .bind(util::now_millis())
+    .execute(database.pool())
+    .await;
+
+    if let Err(err) = result {
+        error!("git:journal record failed: {err}");
+    }
+}
+
+/// Records a change of HEAD between two snapshots, skipping no-ops.
+pub(crate) async fn record_head_change(
+    app: &AppHandle,
+    repository_path: &str,
+    kind: GitOperationKind,
+    before: HeadSnapshot,
+    after: HeadSnapshot,
+    description: String,
+) {
+    if before.oid == after.oid && before.symbolic == after.symbolic {
+        return;
+    }
+
+    let ref_name = match kind {
+        GitOperationKind::Switch => Some("HEAD".to_string()),
+        _ => before.symbolic.clone().or_else(|| Some("HEAD".to_string())),
+    };
+    record_operation(
+        app,
+        repository_path,
+        OperationEntry {
+            kind,
+            ref_name,
+            previous_head: before.symbolic,
+            previous_oid: before.oid,
+            new_oid: after.oid,
+            description,
+            reset_mode: None,
+        },
+    )
+    .await;
+}
+
+fn ref_changed(record: &GitOperationRecord) -> GitErrorResponse {
+    GitErrorResponse::new(
+        GitErrorKind::RefChanged,
+        format!(
+            "the repository changed after `{}`; restore it from the reflog instead",
+            record.description
+        ),
+    )
+}
+
+/// Verifies that HEAD still points where the operation left it.
+fn ensure_head_unchanged(
+    head: &HeadSnapshot,
+    record: &GitOperationRecord,
+) -> Result<(), GitErrorResponse> {
+    let same_ref = match record.ref_name.as_deref() {
+        Some("HEAD") | None => true,
+        Some(ref_name) => head.symbolic.as_deref() == Some(ref_name),
+    };
+    if !same_ref || head.oid != record.new_oid {
+        return Err(ref_changed(record));
+    }
+    Ok(())
+}
+
+fn previous_oid(record: &GitOperationRecord) -> Result<String, GitErrorResponse> {
+    record.previous_oid.clone().ok_or_else(|| {
+        GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            format!("`{}` has no previous state to restore", record.description),
+        )
+    })
+}
+
+async fn undo_args(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    record: &GitOperationRecord,
+) -> Result<Vec<String>, GitErrorResponse> {
+    let head = snapshot_head(app, service, repository_path).await;
+    let args = match record.kind {
+        GitOperationKind::Commit => {
+            ensure_head_unchanged(&head, record)?;
+            vec!["reset".into(), "--soft".into(), previous_oid(record)?]
+        }
+        GitOperationKind::Rebase => {
+            ensure_head_unchanged(&head, record)?;
+            vec!["reset".into(), "--keep".into(), previous_oid(record)?]
+        }
+        GitOperationKind::Reset => {
+            ensure_head_unchanged(&head, record)?;
+            // Discarded changes cannot come back, so hard resets are undone
+            // with `--keep`, which refuses to clobber newer local edits.
+            let mode = match record.reset_mode {
+                Some(GitResetMode::Soft) => "--soft",
+                Some(GitResetMode::Mixed) => "--mixed",
+                _ => "--keep",
+            };
+            vec!["reset".into(), mode.into(), previous_oid(record)?]
+        }
+        GitOperationKind::Switch => {
+            ensure_head_unchanged(&head, record)?;
+            match record
+                .previous_head
+                .as_deref()
+                .and_then(|head| head.strip_prefix("refs/heads/"))
+            {
+                Some(branch) => vec!["switch".into(), branch.to_string()],
+                None => vec!["switch".into(), "--detach".into(), previous_oid(record)?],
+            }
+        }
+        GitOperationKind::Stash => {
+            let stash = resolve_ref(app, service, repository_path, "refs/stash").await;
+            if stash != record.new_oid {
+                return Err(ref_changed(record));
+            }
+            vec!["stash".into(), "pop".into(), "--index".into()]
+        }
+        GitOperationKind::DeleteBranch => {
+            let branch = record
+                .ref_name
+                .as_deref()
+                .and_then(|name| name.strip_prefix("refs/heads/"))
+                .ok_or_else(|| ref_changed(record))?;
+            vec!["branch".into(), branch.to_string(), previous_oid(record)?]
+        }
+    };
+    Ok(args)
+}
+
+#[tauri::command]
+pub async fn git_reflog(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitReflogRequest,
+) -> Result<Vec<GitReflogEntry>, GitErrorResponse> {
+    let ref_name = util::sanitize_revision(request.ref_name.as_deref().unwrap_or("HEAD"), "ref")
+        .map_err(GitErrorResponse::from)?;
+    let limit = request.limit.unwrap_or(DEFAULT_REFLOG_LIMIT);
+
+    // One extra entry supplies the old hash of the last returned entry.
+    let outcome = run_git_capture(
+        &app,
+        &service,
+        &request.repository_path,
+        vec![
+            "reflog".into(),
+            "show".into(),
+            "--date=unix".into(),
+            "--format=%H%x09%gd%x09%gs".into(),
+            format!("-n{}", limit + 1),
+            ref_name,
+            "--".into(),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to read reflog",
+        ));
+    }
+
+    let mut entries = util::parse_reflog(&outcome.stdout);
+    entries.truncate(limit);
+    Ok(entries)
+}
+
+#[tauri::command]
+pub async fn git_operations_list(
+    database: State<'_, Database>,
+    repository_path: String,
+) -> Result<Vec<GitOperationRecord>, GitErrorResponse> {
+    let key = repository_key(&repository_path).map_err(GitErrorResponse::from)?;
+    let rows: Vec<OperationRow> = sqlx::query_as(&format!(
+        "SELECT {OPERATION_COLUMNS} FROM git_operations
+         WHERE repository_path = ? ORDER BY created_at DESC LIMIT ?"
+    ))
+    .bind(&key)
+    .bind(OPERATION_LIST_LIMIT)
+    .fetch_all(database.pool())
+    .await
+    .map_err(|err| GitErrorResponse::from(GitError::from(err)))?;
+
+    rows.into_iter()
+        .map(|row| record_from_row(row).map_err(GitErrorResponse::from))
+        .collect()
+}
+
+/// Restores the ref state from before the most recent operation the app
+/// performed in this repository that has not been undone yet.
+#[tauri::command]
+pub async fn git_undo_last_operation(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    database: State<'_, Database>,
+    repository_path: String,
+) -> Result<GitOperationRecord, GitErrorResponse> {
+    let key = repository_key(&repository_path).map_err(GitErrorResponse::from)?;
+    let row: Option<OperationRow> = sqlx::query_as(&format!(
+        "SELECT {OPERATION_COLUMNS} FROM git_operations
+         WHERE repository_path = ? AND undone_at IS NULL
+         ORDER BY created_at DESC LIMIT 1"
+    ))
+    .bind(&key)
+    .fetch_optional(database.pool())
+    .await
+    .map_err(|err| GitErrorResponse::from(GitError::from(err)))?;
+    let mut record = row
+        .map(record_from_row)
+        .transpose()
+        .map_err(GitErrorResponse::from)?
+        .ok_or_else(|| {
+            GitErrorResponse::new(GitErrorKind::InvalidArgument, "there is nothing to undo")
+        })?;
+
+    let args = undo_args(&app, &service, &repository_path, &record).await?;
+    let outcome = run_git_capture(&app, &service, &repository_path, args, None)
+        .await
+        .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to undo operation",
+        ));
+    }
+
+    let undone_at = util::now_millis();
+    sqlx::query("UPDATE git_operations SET undone_at = ? WHERE id = ?")
+        .bind(undone_at)
+        .bind(&record.id)
+        .execute(database.pool())
+        .await
+        .map_err(|err| GitErrorResponse::from(GitError::from(err)))?;
+    record.undone_at = Some(undone_at);
+
+    info!("git:undo {} {}", record.kind.as_str(), record.description);
+    Ok(record)
+}
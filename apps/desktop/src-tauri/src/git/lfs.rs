@@ -0,0 +1,332 @@
+use crate::git::{
+    models::{
+        GitCommandHandle, GitCommandOutcome, GitError, GitErrorKind, GitErrorResponse, GitLfsLock,
+        GitLfsPathRequest, GitLfsPointer, GitLfsPullRequest, GitLfsStatus, GitLfsTrackRequest,
+        GitLfsUnlockRequest,
+    },
+    operations::{repository_root, run_git_capture},
+    service::GitService,
+    streaming::run_streaming_command,
+    util,
+};
+use std::fs;
+use tauri::{AppHandle, State};
+
+/// Pointer files are well under this size; anything larger is real content.
+const MAX_POINTER_SIZE: u64 = 1024;
+
+async fn lfs_status(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+) -> Result<GitLfsStatus, GitErrorResponse> {
+    let root = repository_root(app, service, repository_path).await?;
+
+    let attributes = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec![
+            "ls-files".into(),
+            "-z".into(),
+            "--full-name".into(),
+            "--cached".into(),
+            "--others".into(),
+            "--exclude-standard".into(),
+            "--".into(),
+            ":(top,glob)**/.gitattributes".into(),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    let mut sources: Vec<&str> = attributes
+        .stdout
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .collect();
+    sources.sort_unstable();
+    sources.dedup();
+
+    let mut patterns = Vec::new();
+    for source in sources {
+        // Deleted but still in the index.
+        let Ok(content) = fs::read_to_string(root.join(source)) else {
+            continue;
+        };
+        patterns.extend(util::parse_lfs_attributes(&content, source));
+    }
+
+    let version = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec!["lfs".into(), "version".into()],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    let installed = version.success;
+
+    let mut files = Vec::new();
+    if installed && !patterns.is_empty() {
+        let listing = run_git_capture(
+            app,
+            service,
+            repository_path,
+            vec!["lfs".into(), "ls-files".into(), "--long".into()],
+            None,
+        )
+        .await
+        .map_err(GitErrorResponse::from)?;
+        if listing.success {
+            files = util::parse_lfs_files(&listing.stdout);
+        }
+    }
+
+    Ok(GitLfsStatus {
+        installed,
+        version: util::parse_lfs_version(&version.stdout),
+        enabled: !patterns.is_empty(),
+        patterns,
+        files,
+    })
+}
+
+fn lfs_patterns(patterns: &[String]) -> Result<Vec<String>, GitErrorResponse> {
+    if patterns.is_empty() {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            "no patterns provided",
+        ));
+    }
+    patterns
+        .iter()
+        .map(|pattern| {
+            let pattern =
+                util::sanitize_arg(pattern.trim(), "pattern").map_err(GitErrorResponse::from)?;
+            if pattern.starts_with('-') {
+                return Err(GitErrorResponse::new(
+                    GitErrorKind::InvalidArgument,
+                    format!("pattern may not start with `-`, got `{pattern}`"),
+                ));
+            }
+            Ok(pattern)
+        })
+        .collect()
+}
+
+fn lfs_path(path: &str) -> Result<String, GitErrorResponse> {
+    let path = util::sanitize_arg(path.trim(), "path").map_err(GitErrorResponse::from)?;
+    if path.starts_with('-') {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            format!("path may not start with `-`, got `{path}`"),
+        ));
+    }
+    Ok(path)
+}
+
+#[tauri::command]
+pub async fn git_lfs_status(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    repository_path: String,
+) -> Result<GitLfsStatus, GitErrorResponse> {
+    lfs_status(&app, &service, &repository_path).await
+}
+
+/// Returns the pointer stored at `path` in the working tree, or `None` when
+/// the file holds real content.
+#[tauri::command]
+pub async fn git_lfs_pointer(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitLfsPathRequest,
+) -> Result<Option<GitLfsPointer>, GitErrorResponse> {
+    let root = repository_root(&app, &service, &request.repository_path).await?;
+    let file = util::repository_file(&root, &request.path).map_err(GitErrorResponse::from)?;
+
+    let metadata = fs::metadata(&file).map_err(|e| GitErrorResponse::from(GitError::Io(e)))?;
+    if !metadata.is_file() || metadata.len() > MAX_POINTER_SIZE {
+        return Ok(None);
+    }
+    let content = fs::read(&file).map_err(|e| GitErrorResponse::from(GitError::Io(e)))?;
+    Ok(std::str::from_utf8(&content)
+        .ok()
+        .and_then(util::parse_lfs_pointer))
+}
+
+#[tauri::command]
+pub async fn git_lfs_track(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitLfsTrackRequest,
+) -> Result<GitLfsStatus, GitErrorResponse> {
+    let mut args = vec!["lfs".into(), "track".into()];
+    if request.lockable.unwrap_or(false) {
+        args.push("--lockable".into());
+    }
+    args.extend(lfs_patterns(&request.patterns)?);
+
+    let outcome = run_git_capture(&app, &service, &request.repository_path, args, None)
+        .await
+        .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to track patterns with LFS",
+        ));
+    }
+    lfs_status(&app, &service, &request.repository_path).await
+}
+
+#[tauri::command]
+pub async fn git_lfs_untrack(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitLfsTrackRequest,
+) -> Result<GitLfsStatus, GitErrorResponse> {
+    let mut args = vec!["lfs".into(), "untrack".into()];
+    args.extend(lfs_patterns(&request.patterns)?);
+
+    let outcome = run_git_capture(&app, &service, &request.repository_path, args, None)
+        .await
+        .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to untrack LFS patterns",
+        ));
+    }
+    lfs_status(&app, &service, &request.repository_path).await
+}
+
+/// Downloads LFS content for the current checkout, streaming progress as
+/// `git://stream` events.
+#[tauri::command]
+pub async fn git_lfs_pull(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitLfsPullRequest,
+) -> Result<GitCommandHandle, GitErrorResponse> {
+    let GitLfsPullRequest {
+        mut stream,
+        include,
+        exclude,
+    } = request;
+    if stream.branch.take().is_some() {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            "lfs pull does not take a branch",
+        ));
+    }
+
+    let mut args = vec!["lfs".into(), "pull".into()];
+    if let Some(include) = include.filter(|paths| !paths.is_empty()) {
+        args.push(format!(
+            "--include={}",
+            util::sanitize_arg(&include.join(","), "include").map_err(GitErrorResponse::from)?
+        ));
+    }
+    if let Some(exclude) = exclude.filter(|paths| !paths.is_empty()) {
+        args.push(format!(
+            "--exclude={}",
+            util::sanitize_arg(&exclude.join(","), "exclude").map_err(GitErrorResponse::from)?
+        ));
+    }
+
+    run_streaming_command(app, service, stream, args).await
+}
+
+#[tauri::command]
+pub async fn git_lfs_locks(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    repository_path: String,
+) -> Result<Vec<GitLfsLock>, GitErrorResponse> {
+    let outcome = run_git_capture(
+        &app,
+        &service,
+        &repository_path,
+        vec!["lfs".into(), "locks".into(), "--json".into()],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to list LFS locks",
+        ));
+    }
+    util::parse_lfs_locks(&outcome.stdout).map_err(GitErrorResponse::from)
+}
+
+#[tauri::command]
+pub async fn git_lfs_lock(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitLfsPathRequest,
+) -> Result<GitLfsLock, GitErrorResponse> {
+    let outcome = run_git_capture(
+        &app,
+        &service,
+        &request.repository_path,
+        vec![
+            "lfs".into(),
+            "lock".into(),
+            "--json".into(),
+            lfs_path(&request.path)?,
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to lock file",
+        ));
+    }
+    util::parse_lfs_lock(&outcome.stdout).map_err(GitErrorResponse::from)
+}
+
+/// Releases a lock by path or id; `force` breaks locks held by others.
+#[tauri::command]
+pub async fn git_lfs_unlock(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitLfsUnlockRequest,
+) -> Result<GitCommandOutcome, GitErrorResponse> {
+    let mut args = vec!["lfs".into(), "unlock".into()];
+    if request.force.unwrap_or(false) {
+        args.push("--force".into());
+    }
+    match (request.path.as_deref(), request.id.as_deref()) {
+        (Some(path), None) => args.push(lfs_path(path)?),
+        (None, Some(id)) => {
+            args.push(format!(
+                "--id={}",
+                util::sanitize_arg(id.trim(), "id").map_err(GitErrorResponse::from)?
+            ));
+        }
+        _ => {
+            return Err(GitErrorResponse::new(
+                GitErrorKind::InvalidArgument,
+                "provide either a path or a lock id",
+            ))
+        }
+    }
+
+    let outcome = run_git_capture(&app, &service, &request.repository_path, args, None)
+        .await
+        .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to unlock file",
+        ));
+    }
+    Ok(outcome)
+}
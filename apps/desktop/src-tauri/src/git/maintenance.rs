@@ -0,0 +1,293 @@
+use crate::git::{
+    models::{
+        GitCommandHandle, GitError, GitErrorKind, GitErrorResponse, GitFsckRequest, GitGcRequest,
+        GitIndexLock, GitIndexLockRemoveRequest, GitMaintenanceRequest, GitMaintenanceRunRequest,
+        GitSizeReport, GitSizeReportRequest, GitStreamRequest,
+    },
+    operations::{repository_root, run_git_capture},
+    service::GitService,
+    streaming::run_streaming_command,
+    util,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+use tauri::{AppHandle, State};
+
+const DEFAULT_BLOB_LIMIT: usize = 20;
+const MAX_BLOB_LIMIT: usize = 200;
+/// Git holds `index.lock` for the length of one command; a lock this old
+/// that no running git process can be holding is left over from a crash.
+const STALE_LOCK_AGE_MS: i64 = 10 * 60 * 1000;
+
+async fn capture(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    args: Vec<String>,
+    failure: &str,
+) -> Result<String, GitErrorResponse> {
+    let outcome = run_git_capture(app, service, repository_path, args, None)
+        .await
+        .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(&outcome, failure));
+    }
+    Ok(outcome.stdout)
+}
+
+/// Object counts and the largest blobs anywhere in the object database,
+/// with a path each was committed under.
+#[tauri::command]
+pub async fn git_size_report(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitSizeReportRequest,
+) -> Result<GitSizeReport, GitErrorResponse> {
+    let counts = capture(
+        &app,
+        &service,
+        &request.repository_path,
+        vec!["count-objects".into(), "-v".into()],
+        "failed to count objects",
+    )
+    .await?;
+    let objects = util::parse_count_objects(&counts);
+
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_BLOB_LIMIT)
+        .clamp(1, MAX_BLOB_LIMIT);
+    let sizes = capture(
+        &app,
+        &service,
+        &request.repository_path,
+        vec![
+            "cat-file".into(),
+            "--batch-all-objects".into(),
+            util::BLOB_SIZE_FORMAT.into(),
+        ],
+        "failed to read object sizes",
+    )
+    .await?;
+    let mut largest_blobs = util::parse_largest_blobs(&sizes, limit);
+
+    if !largest_blobs.is_empty() {
+        let objects = capture(
+            &app,
+            &service,
+            &request.repository_path,
+            vec!["rev-list".into(), "--objects".into(), "--all".into()],
+            "failed to list reachable objects",
+        )
+        .await?;
+        util::assign_blob_paths(&mut largest_blobs, &objects);
+    }
+
+    Ok(GitSizeReport {
+        total_size: objects.loose_size + objects.pack_size + objects.garbage_size,
+        objects,
+        largest_blobs,
+    })
+}
+
+async fn run_maintenance(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitMaintenanceRequest,
+    args: Vec<String>,
+) -> Result<GitCommandHandle, GitErrorResponse> {
+    run_streaming_command(
+        app,
+        service,
+        GitStreamRequest {
+            repository_path: request.repository_path,
+            remote: None,
+            branch: None,
+            auth: None,
+            command_id: request.command_id,
+        },
+        args,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn git_gc(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitGcRequest,
+) -> Result<GitCommandHandle, GitErrorResponse> {
+    let mut args = vec!["gc".into(), "--progress".into()];
+    if request.aggressive.unwrap_or(false) {
+        args.push("--aggressive".into());
+    }
+    run_maintenance(app, service, request.maintenance, args).await
+}
+
+/// Runs `git maintenance run` with the given tasks, or the tasks configured
+/// for the repository.
+#[tauri::command]
+pub async fn git_maintenance_run(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitMaintenanceRunRequest,
+) -> Result<GitCommandHandle, GitErrorResponse> {
+    let mut args = vec!["maintenance".into(), "run".into()];
+    let mut tasks = request.tasks.unwrap_or_default();
+    tasks.dedup();
+    args.extend(tasks.iter().map(|task| task.as_arg().to_string()));
+    run_maintenance(app, service, request.maintenance, args).await
+}
+
+#[tauri::command]
+pub async fn git_fsck(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitFsckRequest,
+) -> Result<GitCommandHandle, GitErrorResponse> {
+    let mut args = vec!["fsck".into(), "--progress".into()];
+    if request.unreachable.unwrap_or(false) {
+        args.push("--unreachable".into());
+    }
+    run_maintenance(app, service, request.maintenance, args).await
+}
+
+/// Removes unreachable loose objects older than `gc.pruneExpire`.
+#[tauri::command]
+pub async fn git_prune(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitMaintenanceRequest,
+) -> Result<GitCommandHandle, GitErrorResponse> {
+    run_maintenance(
+        app,
+        service,
+        request,
+        vec!["prune".into(), "--progress".into(), "--verbose".into()],
+    )
+    .await
+}
+
+async fn index_lock_path(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+) -> Result<PathBuf, GitErrorResponse> {
+    let path = capture(
+        app,
+        service,
+        repository_path,
+        vec!["rev-parse".into(), "--git-path".into(), "index.lock".into()],
+        "failed to locate the index",
+    )
+    .await?;
+    // Relative to the working directory unless git printed an absolute path.
+    let working_dir = util::canonicalize_path(repository_path).map_err(GitErrorResponse::from)?;
+    Ok(working_dir.join(path))
+}
+
+/// Whether a git process is running inside one of `dirs`, judged by its
+/// working directory. `None` where running processes cannot be inspected.
+#[cfg(target_os = "linux")]
+fn git_process_running(dirs: &[PathBuf]) -> Option<bool> {
+    let entries = fs::read_dir("/proc").ok()?;
+    Some(entries.flatten().any(|entry| {
+        let process = entry.path();
+        let is_git = fs::read_to_string(process.join("comm"))
+            .is_ok_and(|name| name.trim() == "git" || name.trim().starts_with("git-"));
+        is_git
+            && fs::read_link(process.join("cwd"))
+                .is_ok_and(|cwd| dirs.iter().any(|dir| cwd.starts_with(dir)))
+    }))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn git_process_running(_dirs: &[PathBuf]) -> Option<bool> {
+    None
+}
+
+/// Directories a git process holding `lock` would run in: the worktree and
+/// the git directory the lock lives in.
+async fn lock_owner_dirs(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    lock: &Path,
+) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Ok(root) = repository_root(app, service, repository_path).await {
+        dirs.push(root);
+    }
+    if let Some(git_dir) = lock.parent().and_then(|dir| fs::canonicalize(dir).ok()) {
+        dirs.push(git_dir);
+    }
+    dirs
+}
+
+/// A lock only counts as stale once it is old and no git process is known
+/// to be running in the repository; where that cannot be checked, removal
+/// always needs `force`.
+fn index_lock(path: PathBuf, owner_dirs: &[PathBuf]) -> GitIndexLock {
+    let age_ms = fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|modified| (util::now_millis() - modified.as_millis() as i64).max(0));
+    let exists = path.exists();
+    let stale = exists
+        && age_ms.is_some_and(|age| age >= STALE_LOCK_AGE_MS)
+        && git_process_running(owner_dirs) == Some(false);
+    GitIndexLock {
+        exists,
+        stale,
+        path: path.to_string_lossy().to_string(),
+        age_ms,
+    }
+}
+
+async fn lock_status(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+) -> Result<(PathBuf, GitIndexLock), GitErrorResponse> {
+    let path = index_lock_path(app, service, repository_path).await?;
+    let owner_dirs = lock_owner_dirs(app, service, repository_path, &path).await;
+    let lock = index_lock(path.clone(), &owner_dirs);
+    Ok((path, lock))
+}
+
+/// Reports whether the worktree's `index.lock` exists and looks abandoned.
+#[tauri::command]
+pub async fn git_index_lock_status(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    repository_path: String,
+) -> Result<GitIndexLock, GitErrorResponse> {
+    let (_, lock) = lock_status(&app, &service, &repository_path).await?;
+    Ok(lock)
+}
+
+/// Deletes a stale `index.lock`. Locks that are recent, or that a running
+/// git process may hold, are only removed with `force`.
+#[tauri::command]
+pub async fn git_index_lock_remove(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitIndexLockRemoveRequest,
+) -> Result<GitIndexLock, GitErrorResponse> {
+    let (path, lock) = lock_status(&app, &service, &request.repository_path).await?;
+    if !lock.exists {
+        return Ok(lock);
+    }
+    if !lock.stale && !request.force.unwrap_or(false) {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::LockContention,
+            "index.lock may belong to a git process that is still running",
+        ));
+    }
+    fs::remove_file(&path).map_err(|e| GitErrorResponse::from(GitError::Io(e)))?;
+    Ok(index_lock(path, &[]))
+}
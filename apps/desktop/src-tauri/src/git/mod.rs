@@ -1,6 +1,28 @@
+pub mod archive;
+pub mod attributes;
 pub mod auth;
+pub mod bisect;
+pub mod commit_message;
+pub mod compare;
+pub mod config;
+pub mod credential_server;
+pub mod credentials;
+pub mod hooks;
+pub mod ignore;
+pub mod journal;
+pub mod lfs;
+pub mod maintenance;
 pub mod models;
 pub mod operations;
+pub mod patch;
+pub mod policy;
+pub mod reset;
+pub mod search;
 pub mod service;
+pub mod sparse;
+pub mod ssh;
+pub mod state;
+pub mod stats;
 pub mod streaming;
 pub mod util;
+pub mod workspace;
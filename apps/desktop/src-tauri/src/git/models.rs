@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -18,20 +18,153 @@ pub enum GitError {
     MissingRepository,
     #[error("invalid argument: {0}")]
     InvalidArgument(String),
+    #[error("host key for {0} is not trusted")]
+    UntrustedHostKey(String),
+    #[error("database error: {0}")]
+    Database(String),
+    #[error("keyring error: {0}")]
+    Keyring(String),
+    #[error("credential vault error: {0}")]
+    Vault(String),
+    #[error("failed to serialize data: {0}")]
+    Serialization(#[from] serde_json::Error),
 }
 
-#[derive(Debug, Serialize)]
+impl From<sqlx::Error> for GitError {
+    fn from(value: sqlx::Error) -> Self {
+        Self::Database(value.to_string())
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitErrorKind {
+    AuthenticationFailed,
+    HostKeyVerification,
+    MergeConflict,
+    NoUpstream,
+    DirtyWorktree,
+    LockContention,
+    NonFastForward,
+    BranchNotMerged,
+    RefNotFound,
+    RefChanged,
+    NotARepository,
+    Network,
+    MissingGit,
+    InvalidArgument,
+    InvalidPath,
+    Io,
+    Database,
+    Parse,
+    Unknown,
+}
+
+impl GitErrorKind {
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::AuthenticationFailed => {
+                Some("Check the stored credentials or SSH key for this remote.")
+            }
+            Self::HostKeyVerification => {
+                Some("Confirm the remote host key before retrying.")
+            }
+            Self::MergeConflict => {
+                Some("Resolve the conflicting files, stage them and continue the operation.")
+            }
+            Self::NoUpstream => Some("Set an upstream branch or choose a remote and branch."),
+            Self::DirtyWorktree => {
+                Some("Commit or stash your local changes before running this operation.")
+            }
+            Self::LockContention => Some(
+                "Another git process is using the repository. Wait for it to finish or remove the stale lock file.",
+            ),
+            Self::NonFastForward => Some("Pull or fetch the remote changes before pushing."),
+            Self::BranchNotMerged => {
+                Some("Merge the branch first or force the deletion.")
+            }
+            Self::RefNotFound => Some("Check that the branch, tag or commit exists."),
+            Self::RefChanged => {
+                Some("The branch moved since this operation; use the reflog to restore it.")
+            }
+            Self::NotARepository => Some("Open a folder that contains a git repository."),
+            Self::Network => Some("Check the network connection and the remote URL."),
+            Self::MissingGit => Some("Install git or configure its path in the settings."),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GitErrorDetails {
+    pub paths: Vec<String>,
+    pub lock_path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GitErrorResponse {
     pub message: String,
+    pub kind: GitErrorKind,
+    pub hint: Option<&'static str>,
+    pub details: Box<GitErrorDetails>,
+    pub exit_code: Option<i32>,
+    pub stderr: Option<String>,
 }
 
-impl From<GitError> for GitErrorResponse {
-    fn from(value: GitError) -> Self {
+impl GitErrorResponse {
+    pub fn new(kind: GitErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            kind,
+            hint: kind.hint(),
+            details: Box::default(),
+            exit_code: None,
+            stderr: None,
+        }
+    }
+
+    /// Classifies the stderr of a finished git command; `fallback` is used as
+    /// the message when git printed nothing.
+    pub fn from_stderr(stderr: &str, exit_code: Option<i32>, fallback: &str) -> Self {
+        let (kind, details) = crate::git::util::classify_stderr(stderr);
+        let trimmed = stderr.trim();
         Self {
-            message: value.to_string(),
+            message: if trimmed.is_empty() {
+                fallback.to_string()
+            } else {
+                trimmed.to_string()
+            },
+            kind,
+            hint: kind.hint(),
+            details: Box::new(details),
+            exit_code,
+            stderr: Some(stderr.to_string()),
         }
     }
+
+    pub fn from_outcome(outcome: &GitCommandOutcome, fallback: &str) -> Self {
+        Self::from_stderr(&outcome.stderr, outcome.exit_code, fallback)
+    }
+}
+
+impl From<GitError> for GitErrorResponse {
+    fn from(value: GitError) -> Self {
+        let kind = match &value {
+            GitError::MissingGit => GitErrorKind::MissingGit,
+            GitError::InvalidPath(_) => GitErrorKind::InvalidPath,
+            GitError::Io(_) | GitError::Spawn(_) | GitError::Keyring(_) | GitError::Vault(_) => {
+                GitErrorKind::Io
+            }
+            GitError::Parse(_) | GitError::Serialization(_) => GitErrorKind::Parse,
+            GitError::MissingRepository => GitErrorKind::NotARepository,
+            GitError::InvalidArgument(_) => GitErrorKind::InvalidArgument,
+            GitError::UntrustedHostKey(_) => GitErrorKind::HostKeyVerification,
+            GitError::Database(_) => GitErrorKind::Database,
+        };
+        Self::new(kind, value.to_string())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -81,6 +214,9 @@ pub struct GitStatusResponse {
     pub conflicts: Vec<GitFileChange>,
     pub untracked: Vec<String>,
     pub is_clean: bool,
+    /// Present when requested and sparse checkout is enabled for the
+    /// worktree.
+    pub sparse: Option<GitSparseCheckoutState>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -186,6 +322,7 @@ pub struct GitCommandOutcome {
     pub success: bool,
     pub stdout: String,
     pub stderr: String,
+    pub error: Option<GitErrorResponse>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -222,6 +359,7 @@ pub struct GitStreamEvent {
     pub data: Option<String>,
     pub exit_code: Option<i32>,
     pub success: Option<bool>,
+    pub error: Option<GitErrorResponse>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -286,6 +424,52 @@ pub struct GitStreamRequest {
     pub command_id: Option<String>,
 }
 
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitPullStrategy {
+    Merge,
+    Rebase,
+    FfOnly,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitPullRequest {
+    #[serde(flatten)]
+    pub stream: GitStreamRequest,
+    pub strategy: Option<GitPullStrategy>,
+    pub autostash: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitFetchRequest {
+    #[serde(flatten)]
+    pub stream: GitStreamRequest,
+    pub prune: Option<bool>,
+    pub tags: Option<bool>,
+    pub refspecs: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSyncRequest {
+    pub repository_path: String,
+    pub remote: Option<String>,
+    pub prune: Option<bool>,
+    pub auth: Option<crate::git::auth::GitAuth>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSyncResponse {
+    pub branch: Option<String>,
+    pub upstream: Option<String>,
+    pub fetch: GitCommandOutcome,
+    pub incoming: Vec<GitGraphEntry>,
+    pub outgoing: Vec<GitGraphEntry>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GitStageRequest {
@@ -298,6 +482,8 @@ pub struct GitStageRequest {
 pub struct GitCommitRequest {
     pub repository_path: String,
     pub message: String,
+    /// Commit even when the message fails the repository's commit lint.
+    pub skip_lint: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -307,8 +493,1458 @@ pub struct GitCommitDetailsRequest {
     pub commit: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitCredentialBackend {
+    Keyring,
+    Vault,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitStoredCredential {
+    pub host: String,
+    pub username: String,
+    pub backend: GitCredentialBackend,
+    pub redacted_secret: String,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCredentialRequest {
+    pub host: String,
+    pub username: String,
+    pub secret: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSshHostKey {
+    pub key_type: String,
+    pub fingerprint: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSshHostKeyEvent {
+    pub command_id: Option<String>,
+    pub host: String,
+    pub port: Option<u16>,
+    pub keys: Vec<GitSshHostKey>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSshTrustRequest {
+    pub host: String,
+    pub port: Option<u16>,
+    pub fingerprint: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSshAgentKey {
+    pub bits: Option<u32>,
+    pub fingerprint: String,
+    pub comment: String,
+    pub key_type: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSshAgentInfo {
+    pub running: bool,
+    pub socket: Option<String>,
+    pub keys: Vec<GitSshAgentKey>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSshKeyFile {
+    pub private_key_path: String,
+    pub public_key_path: String,
+    pub key_type: String,
+    pub comment: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct GitCommandConfig {
     pub executable: GitExecutable,
     pub working_dir: PathBuf,
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitBisectStartRequest {
+    pub repository_path: String,
+    pub good: String,
+    pub bad: String,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitBisectVerdict {
+    Good,
+    Bad,
+    Skip,
+}
+
+impl GitBisectVerdict {
+    pub fn as_arg(&self) -> &'static str {
+        match self {
+            Self::Good => "good",
+            Self::Bad => "bad",
+            Self::Skip => "skip",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitBisectMarkRequest {
+    pub repository_path: String,
+    pub verdict: GitBisectVerdict,
+    pub commit: Option<String>,
+}
+
+/// Test command for `git bisect run`: either a saved run configuration from
+/// the `runs` table or an inline command such as a detected run suggestion.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitBisectRunRequest {
+    pub repository_path: String,
+    pub run_id: Option<String>,
+    pub command: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    pub cwd: Option<String>,
+    pub command_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GitBisectStatus {
+    pub active: bool,
+    pub current: Option<String>,
+    pub current_subject: Option<String>,
+    pub bad: Option<String>,
+    pub good: Vec<String>,
+    pub skipped: Vec<String>,
+    pub remaining_revisions: Option<u32>,
+    pub remaining_steps: Option<u32>,
+    pub first_bad_commit: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitReflogRequest {
+    pub repository_path: String,
+    pub ref_name: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitReflogEntry {
+    pub selector: String,
+    pub index: usize,
+    pub old_hash: Option<String>,
+    pub new_hash: String,
+    pub action: String,
+    pub message: String,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitOperationKind {
+    Commit,
+    Switch,
+    Stash,
+    Rebase,
+    DeleteBranch,
+    Reset,
+}
+
+impl GitOperationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Commit => "commit",
+            Self::Switch => "switch",
+            Self::Stash => "stash",
+            Self::Rebase => "rebase",
+            Self::DeleteBranch => "delete_branch",
+            Self::Reset => "reset",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "commit" => Some(Self::Commit),
+            "switch" => Some(Self::Switch),
+            "stash" => Some(Self::Stash),
+            "rebase" => Some(Self::Rebase),
+            "delete_branch" => Some(Self::DeleteBranch),
+            "reset" => Some(Self::Reset),
+            _ => None,
+        }
+    }
+}
+
+/// A git operation performed by the app, with the ref state needed to undo it.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitOperationRecord {
+    pub id: String,
+    pub repository_path: String,
+    pub kind: GitOperationKind,
+    pub ref_name: Option<String>,
+    pub previous_head: Option<String>,
+    pub previous_oid: Option<String>,
+    pub new_oid: Option<String>,
+    pub description: String,
+    pub reset_mode: Option<GitResetMode>,
+    pub created_at: i64,
+    pub undone_at: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitResetMode {
+    Soft,
+    Mixed,
+    Hard,
+    Keep,
+}
+
+impl GitResetMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Soft => "soft",
+            Self::Mixed => "mixed",
+            Self::Hard => "hard",
+            Self::Keep => "keep",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "soft" => Some(Self::Soft),
+            "mixed" => Some(Self::Mixed),
+            "hard" => Some(Self::Hard),
+            "keep" => Some(Self::Keep),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitResetPreviewRequest {
+    pub repository_path: String,
+    pub target: String,
+    pub mode: GitResetMode,
+}
+
+/// What a reset would change. Hard resets return a single-use token that
+/// [`GitResetRequest`] must echo back.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitResetPreview {
+    pub mode: GitResetMode,
+    pub current_oid: Option<String>,
+    pub target_oid: String,
+    pub lost_changes: Vec<GitFileChange>,
+    pub dropped_commits: Vec<GitGraphEntry>,
+    pub confirmation_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitResetRequest {
+    pub repository_path: String,
+    pub target: String,
+    pub mode: GitResetMode,
+    pub confirmation_token: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitResetResponse {
+    pub mode: GitResetMode,
+    pub previous_oid: Option<String>,
+    pub new_oid: String,
+}
+
+/// A multi-step git operation that is waiting for the user.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitInProgressOperation {
+    Merge,
+    Rebase,
+    Am,
+    CherryPick,
+    Revert,
+    Bisect,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GitOperationState {
+    pub operation: Option<GitInProgressOperation>,
+    pub conflicts: Vec<String>,
+    pub step: Option<u32>,
+    pub total_steps: Option<u32>,
+    pub current_subject: Option<String>,
+}
+
+/// Exports commits with `git format-patch`. A `range` without `..` exports
+/// just that commit.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitFormatPatchRequest {
+    pub repository_path: String,
+    pub range: String,
+    pub output_dir: String,
+    pub cover_letter: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitPatchPreviewRequest {
+    pub repository_path: String,
+    pub patch_path: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitPatchFileStat {
+    pub path: String,
+    pub additions: Option<u32>,
+    pub deletions: Option<u32>,
+    pub binary: bool,
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitPatchPreview {
+    pub files: Vec<GitPatchFileStat>,
+    pub applies_cleanly: bool,
+    pub check_output: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitAmRequest {
+    pub repository_path: String,
+    pub patch_paths: Vec<String>,
+    pub three_way: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitAmAction {
+    Continue,
+    Skip,
+    Abort,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitAmActionRequest {
+    pub repository_path: String,
+    pub action: GitAmAction,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitAmResponse {
+    pub outcome: GitCommandOutcome,
+    pub state: GitOperationState,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitSearchMode {
+    Message,
+    Author,
+    Hash,
+    Content,
+    Regex,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSearchRequest {
+    pub repository_path: String,
+    pub query: String,
+    pub mode: GitSearchMode,
+    pub limit: Option<u32>,
+}
+
+/// Commit metadata as read from `git log` for searching and indexing.
+#[derive(Debug, Clone)]
+pub struct GitCommitRecord {
+    pub hash: String,
+    pub author: String,
+    pub email: String,
+    pub timestamp: i64,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Character range of a match inside [`GitSearchMatch::snippet`].
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSearchHighlight {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSearchMatch {
+    pub commit: String,
+    pub author: String,
+    pub email: String,
+    pub timestamp: i64,
+    pub subject: String,
+    pub snippet: String,
+    pub highlights: Vec<GitSearchHighlight>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSearchResponse {
+    pub matches: Vec<GitSearchMatch>,
+    pub from_index: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitStatsRequest {
+    pub repository_path: String,
+    /// Recompute even when statistics for the current HEAD are cached.
+    pub refresh: Option<bool>,
+}
+
+/// One non-merge commit with its `--numstat` lines.
+#[derive(Debug, Clone)]
+pub struct GitNumstatCommit {
+    pub author: String,
+    pub email: String,
+    pub timestamp: i64,
+    pub files: Vec<GitPatchFileStat>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitAuthorStats {
+    pub name: String,
+    pub email: String,
+    pub commits: u32,
+    pub additions: u64,
+    pub deletions: u64,
+    pub first_commit_at: i64,
+    pub last_commit_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitStatsWindow {
+    /// Length of the window in days; `None` covers the whole history.
+    pub days: Option<u32>,
+    pub commits: u32,
+    pub additions: u64,
+    pub deletions: u64,
+    pub active_authors: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitFileChurn {
+    pub path: String,
+    pub commits: u32,
+    pub additions: u64,
+    pub deletions: u64,
+    pub authors: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitDirectoryOwner {
+    pub name: String,
+    pub email: String,
+    pub changes: u64,
+    pub share: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitDirectoryOwnership {
+    /// Top-level directory, or an empty string for files at the root.
+    pub path: String,
+    /// Smallest number of authors responsible for more than half of the
+    /// changed lines.
+    pub bus_factor: u32,
+    pub changes: u64,
+    pub owners: Vec<GitDirectoryOwner>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitRepositoryStats {
+    pub head_oid: String,
+    pub computed_at: i64,
+    pub total_commits: u32,
+    pub authors: Vec<GitAuthorStats>,
+    pub windows: Vec<GitStatsWindow>,
+    pub hotspots: Vec<GitFileChurn>,
+    pub directories: Vec<GitDirectoryOwnership>,
+    #[serde(default)]
+    pub cached: bool,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSparseCheckoutState {
+    pub enabled: bool,
+    pub cone: bool,
+    /// Top of the worktree; patterns and excluded directories are relative
+    /// to it. Unset when sparse checkout is disabled.
+    pub root: Option<String>,
+    /// Directories in the sparse set (cone mode) or the raw patterns.
+    pub patterns: Vec<String>,
+    /// Tracked directories left out of the working tree, outermost only.
+    /// Only computed in cone mode, and reused while `HEAD`'s tree and the
+    /// patterns stay the same.
+    pub excluded_directories: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSparseCheckoutInitRequest {
+    pub repository_path: String,
+    /// Defaults to cone mode.
+    pub cone: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSparseCheckoutSetRequest {
+    pub repository_path: String,
+    pub directories: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitCloneFilter {
+    BlobNone,
+    TreeZero,
+}
+
+impl GitCloneFilter {
+    pub fn as_arg(&self) -> &'static str {
+        match self {
+            Self::BlobNone => "--filter=blob:none",
+            Self::TreeZero => "--filter=tree:0",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCloneRequest {
+    /// Existing directory the repository is cloned into.
+    pub parent_path: String,
+    pub url: String,
+    /// Name of the new directory; git derives it from the URL when omitted.
+    pub directory: Option<String>,
+    pub branch: Option<String>,
+    pub filter: Option<GitCloneFilter>,
+    /// Start with only the files at the repository root checked out.
+    pub sparse: Option<bool>,
+    pub auth: Option<crate::git::auth::GitAuth>,
+    pub command_id: Option<String>,
+}
+
+/// A `filter=lfs` line from a `.gitattributes` file.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitLfsPattern {
+    pub pattern: String,
+    /// Repository-relative path of the `.gitattributes` file declaring it.
+    pub source: String,
+    pub lockable: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitLfsFile {
+    pub path: String,
+    pub oid: String,
+    /// False while only the pointer is checked out.
+    pub downloaded: bool,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GitLfsPointer {
+    pub oid: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitLfsLock {
+    pub id: String,
+    pub path: String,
+    pub owner: Option<String>,
+    pub locked_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitLfsStatus {
+    /// Whether the `git lfs` extension is available.
+    pub installed: bool,
+    pub version: Option<String>,
+    /// Whether any `.gitattributes` file routes paths through LFS.
+    pub enabled: bool,
+    pub patterns: Vec<GitLfsPattern>,
+    pub files: Vec<GitLfsFile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitLfsTrackRequest {
+    pub repository_path: String,
+    pub patterns: Vec<String>,
+    /// Mark the patterns lockable (`git lfs track --lockable`).
+    pub lockable: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitLfsPathRequest {
+    pub repository_path: String,
+    pub path: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitLfsUnlockRequest {
+    pub repository_path: String,
+    pub path: Option<String>,
+    pub id: Option<String>,
+    /// Break a lock held by someone else.
+    pub force: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitLfsPullRequest {
+    #[serde(flatten)]
+    pub stream: GitStreamRequest,
+    pub include: Option<Vec<String>>,
+    pub exclude: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitPathsRequest {
+    pub repository_path: String,
+    pub paths: Vec<String>,
+}
+
+/// Why a path is or is not ignored, from `git check-ignore -v`.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GitIgnoreMatch {
+    pub path: String,
+    pub ignored: bool,
+    /// File declaring the matching pattern (`.gitignore`, `.git/info/exclude`
+    /// or the global excludes file).
+    pub source: Option<String>,
+    pub line: Option<u32>,
+    pub pattern: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitIgnoreTarget {
+    /// A `.gitignore` file, shared through the repository.
+    Gitignore,
+    /// `.git/info/exclude`, local to this clone.
+    InfoExclude,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitIgnoreAddRequest {
+    pub repository_path: String,
+    pub patterns: Vec<String>,
+    pub target: GitIgnoreTarget,
+    /// Directory, relative to `repository_path`, whose `.gitignore` receives
+    /// the patterns; defaults to `repository_path` itself.
+    pub directory: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitIgnoreAddResponse {
+    pub file: String,
+    /// Patterns written; ones already present are skipped.
+    pub added: Vec<String>,
+}
+
+/// Effective attributes of a path, from `git check-attr -a`.
+#[derive(Debug, Serialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GitFileAttributes {
+    pub path: String,
+    /// `set`, `unset`, `auto` or a value, when specified.
+    pub text: Option<String>,
+    pub eol: Option<String>,
+    /// Diff driver, or `unset` when diffs are disabled.
+    pub diff: Option<String>,
+    pub merge: Option<String>,
+    pub binary: bool,
+    pub lfs: bool,
+    pub lockable: bool,
+    /// Every attribute that is specified for the path.
+    pub attributes: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitConfigScope {
+    System,
+    Global,
+    Local,
+    Worktree,
+    /// Set with `-c` for a single command; read-only.
+    Command,
+}
+
+impl GitConfigScope {
+    pub fn as_flag(&self) -> Option<&'static str> {
+        match self {
+            Self::System => Some("--system"),
+            Self::Global => Some("--global"),
+            Self::Local => Some("--local"),
+            Self::Worktree => Some("--worktree"),
+            Self::Command => None,
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "system" => Some(Self::System),
+            "global" => Some(Self::Global),
+            "local" => Some(Self::Local),
+            "worktree" => Some(Self::Worktree),
+            "command" => Some(Self::Command),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitConfigEntry {
+    /// As printed by git: section and variable names are lowercase.
+    pub key: String,
+    pub value: String,
+    pub scope: GitConfigScope,
+    /// Where the value was read from, e.g. `file:.git/config`.
+    pub origin: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitConfigSetRequest {
+    pub repository_path: String,
+    pub key: String,
+    pub value: String,
+    pub scope: GitConfigScope,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitConfigUnsetRequest {
+    pub repository_path: String,
+    pub key: String,
+    pub scope: GitConfigScope,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitPullRebaseMode {
+    False,
+    True,
+    Merges,
+    Interactive,
+}
+
+impl GitPullRebaseMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::False => "false",
+            Self::True => "true",
+            Self::Merges => "merges",
+            Self::Interactive => "interactive",
+        }
+    }
+
+    /// Accepts git's boolean spellings as well as the named modes.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "false" | "no" | "off" | "0" => Some(Self::False),
+            "true" | "yes" | "on" | "1" => Some(Self::True),
+            "merges" | "m" => Some(Self::Merges),
+            "interactive" | "i" => Some(Self::Interactive),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitConfigValue<T> {
+    pub value: T,
+    pub scope: GitConfigScope,
+}
+
+/// Effective values of the settings the app edits directly.
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCommonConfig {
+    pub user_name: Option<GitConfigValue<String>>,
+    pub user_email: Option<GitConfigValue<String>>,
+    pub default_branch: Option<GitConfigValue<String>>,
+    pub pull_rebase: Option<GitConfigValue<GitPullRebaseMode>>,
+    pub signing_key: Option<GitConfigValue<String>>,
+}
+
+/// Fields left out are not changed; an empty string unsets the key.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCommonConfigRequest {
+    pub repository_path: String,
+    pub scope: GitConfigScope,
+    pub user_name: Option<String>,
+    pub user_email: Option<String>,
+    pub default_branch: Option<String>,
+    /// `None` leaves `pull.rebase` unchanged; use `unset_pull_rebase` to
+    /// remove it.
+    pub pull_rebase: Option<GitPullRebaseMode>,
+    #[serde(default)]
+    pub unset_pull_rebase: bool,
+    pub signing_key: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitRunStatus {
+    Succeeded,
+    Failed,
+    /// Rejected by the `git_run` policy before git was started.
+    Denied,
+    /// Git could not be started.
+    Error,
+}
+
+impl GitRunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+            Self::Denied => "denied",
+            Self::Error => "error",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "succeeded" => Some(Self::Succeeded),
+            "failed" => Some(Self::Failed),
+            "denied" => Some(Self::Denied),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+/// One `git_run` invocation from the audit log.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitRunAuditEntry {
+    pub id: String,
+    pub cwd: String,
+    pub args: Vec<String>,
+    pub status: GitRunStatus,
+    pub exit_code: Option<i32>,
+    /// Why the call was denied or failed to start.
+    pub message: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHook {
+    pub name: String,
+    pub path: String,
+    /// Installed under its own name rather than as `<name>.disabled`.
+    pub enabled: bool,
+    /// Git skips hooks without the executable bit on Unix.
+    pub executable: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHooksResponse {
+    pub hooks_path: String,
+    /// Whether `core.hooksPath` points somewhere other than `.git/hooks`.
+    pub custom_hooks_path: bool,
+    pub hooks: Vec<GitHook>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHookRequest {
+    pub repository_path: String,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHookToggleRequest {
+    pub repository_path: String,
+    pub name: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHookContent {
+    pub hook: GitHook,
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHookInstallRequest {
+    pub repository_path: String,
+    /// Directory relative to the repository root holding hook scripts;
+    /// defaults to `.githooks`.
+    pub template_dir: Option<String>,
+    pub overwrite: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHookInstallResponse {
+    pub installed: Vec<String>,
+    /// Hooks left alone because one is already installed.
+    pub skipped: Vec<String>,
+    pub hooks: GitHooksResponse,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitHookAction {
+    Commit,
+    Push,
+    Merge,
+    Rebase,
+    Checkout,
+}
+
+impl GitHookAction {
+    /// Client-side hooks git may run for the action, in order.
+    pub fn hooks(&self) -> &'static [&'static str] {
+        match self {
+            Self::Commit => &[
+                "pre-commit",
+                "prepare-commit-msg",
+                "commit-msg",
+                "reference-transaction",
+                "post-commit",
+            ],
+            Self::Push => &["pre-push", "reference-transaction"],
+            Self::Merge => &[
+                "pre-merge-commit",
+                "prepare-commit-msg",
+                "commit-msg",
+                "reference-transaction",
+                "post-merge",
+            ],
+            Self::Rebase => &[
+                "pre-rebase",
+                "reference-transaction",
+                "post-checkout",
+                "post-rewrite",
+            ],
+            Self::Checkout => &["reference-transaction", "post-checkout"],
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHookPreviewRequest {
+    pub repository_path: String,
+    pub action: GitHookAction,
+}
+
+/// Emitted on `git://hooks` before the app runs an action that triggers
+/// hooks.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHookNotice {
+    pub repository_path: String,
+    pub action: GitHookAction,
+    /// Set when the action streams its output under this id.
+    pub command_id: Option<String>,
+    pub hooks: Vec<String>,
+}
+
+/// Repositories to cover: registered projects, or every repository found
+/// under `directory`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitWorkspaceRequest {
+    pub directory: Option<String>,
+    /// How deep to look for repositories under `directory`.
+    pub max_depth: Option<usize>,
+    /// Repositories processed at once.
+    pub concurrency: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitRepositorySummary {
+    pub path: String,
+    pub name: String,
+    pub branch: Option<String>,
+    pub upstream: Option<String>,
+    pub detached: bool,
+    pub ahead: u32,
+    pub behind: u32,
+    pub staged: u32,
+    pub unstaged: u32,
+    pub conflicts: u32,
+    pub untracked: u32,
+    pub stash_count: u32,
+    /// Modification time of `FETCH_HEAD`, in milliseconds.
+    pub last_fetch_at: Option<i64>,
+    /// Set when the repository could not be read; the other fields are then
+    /// empty.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitWorkspaceFetchRequest {
+    #[serde(flatten)]
+    pub workspace: GitWorkspaceRequest,
+    pub prune: Option<bool>,
+    pub auth: Option<crate::git::auth::GitAuth>,
+    pub batch_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitWorkspaceFetchHandle {
+    pub batch_id: String,
+    pub repositories: Vec<String>,
+}
+
+/// Emitted on `git://workspace-fetch` whenever a repository in a batch
+/// fetch starts or finishes. Output of each fetch streams on `git://stream`
+/// under `command_id`.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitWorkspaceFetchProgress {
+    pub batch_id: String,
+    pub repository_path: String,
+    pub command_id: Option<String>,
+    /// `None` while the fetch is running.
+    pub success: Option<bool>,
+    pub error: Option<String>,
+    pub total: u32,
+    pub completed: u32,
+    pub failed: u32,
+    pub done: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCommitTemplate {
+    pub path: String,
+    pub content: String,
+}
+
+/// Conventional-commit rules for one repository.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GitCommitLintConfig {
+    pub enabled: bool,
+    /// Allowed commit types.
+    pub types: Vec<String>,
+    /// Allowed scopes; any scope is accepted when empty.
+    pub scopes: Vec<String>,
+    pub require_scope: bool,
+    pub max_subject_length: Option<u32>,
+}
+
+impl Default for GitCommitLintConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            types: [
+                "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore",
+                "revert",
+            ]
+            .iter()
+            .map(|kind| kind.to_string())
+            .collect(),
+            scopes: Vec::new(),
+            require_scope: false,
+            max_subject_length: Some(72),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCommitLintConfigRequest {
+    pub repository_path: String,
+    pub config: GitCommitLintConfig,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitCommitLintSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCommitLintWarning {
+    /// Rule identifier such as `type-enum` or `subject-max-length`.
+    pub rule: &'static str,
+    pub severity: GitCommitLintSeverity,
+    pub message: String,
+    /// One-based line of the message the warning refers to.
+    pub line: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCommitFooter {
+    pub token: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitConventionalCommit {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub scope: Option<String>,
+    /// Marked with `!` or a `BREAKING CHANGE` footer.
+    pub breaking: bool,
+    pub description: String,
+    pub body: Option<String>,
+    pub footers: Vec<GitCommitFooter>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCommitLintRequest {
+    pub repository_path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCommitLintResult {
+    /// False when any warning has error severity.
+    pub valid: bool,
+    pub enabled: bool,
+    pub parsed: Option<GitConventionalCommit>,
+    pub warnings: Vec<GitCommitLintWarning>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCommitNameCount {
+    pub name: String,
+    pub count: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCommitSuggestions {
+    /// Most used first.
+    pub types: Vec<GitCommitNameCount>,
+    pub scopes: Vec<GitCommitNameCount>,
+    /// Distinct subjects, newest first.
+    pub recent_messages: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitArchiveFormat {
+    TarGz,
+    Zip,
+}
+
+impl GitArchiveFormat {
+    pub fn as_arg(&self) -> &'static str {
+        match self {
+            Self::TarGz => "--format=tar.gz",
+            Self::Zip => "--format=zip",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitArchiveRequest {
+    pub repository_path: String,
+    /// Commit, tag or branch to export.
+    pub revision: String,
+    pub format: GitArchiveFormat,
+    /// Absolute path of the archive to write.
+    pub output_path: String,
+    /// Only export this directory, relative to `repository_path`. Like git,
+    /// a `repository_path` below the root exports just that subtree.
+    pub subdirectory: Option<String>,
+    /// Directory every entry is placed under, such as `project-1.2/`.
+    pub prefix: Option<String>,
+    pub overwrite: Option<bool>,
+    pub command_id: Option<String>,
+}
+
+/// `git count-objects -v`, with sizes in bytes.
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GitObjectCounts {
+    pub loose_objects: u64,
+    pub loose_size: u64,
+    pub packed_objects: u64,
+    pub packs: u64,
+    pub pack_size: u64,
+    /// Loose objects that are also in a pack and could be pruned.
+    pub prune_packable: u64,
+    pub garbage_files: u64,
+    pub garbage_size: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitLargeBlob {
+    pub oid: String,
+    pub size: u64,
+    /// Bytes the blob takes on disk, after compression and deltas.
+    pub disk_size: u64,
+    /// A path the blob was committed under; `None` for unreachable blobs.
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSizeReportRequest {
+    pub repository_path: String,
+    /// Number of largest blobs to report.
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSizeReport {
+    pub objects: GitObjectCounts,
+    /// Loose, packed and garbage bytes together.
+    pub total_size: u64,
+    pub largest_blobs: Vec<GitLargeBlob>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitMaintenanceRequest {
+    pub repository_path: String,
+    pub command_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitFsckRequest {
+    #[serde(flatten)]
+    pub maintenance: GitMaintenanceRequest,
+    /// Also list objects that nothing references.
+    pub unreachable: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitGcRequest {
+    #[serde(flatten)]
+    pub maintenance: GitMaintenanceRequest,
+    pub aggressive: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum GitMaintenanceTask {
+    Gc,
+    CommitGraph,
+    Prefetch,
+    LooseObjects,
+    IncrementalRepack,
+    PackRefs,
+}
+
+impl GitMaintenanceTask {
+    pub fn as_arg(&self) -> &'static str {
+        match self {
+            Self::Gc => "--task=gc",
+            Self::CommitGraph => "--task=commit-graph",
+            Self::Prefetch => "--task=prefetch",
+            Self::LooseObjects => "--task=loose-objects",
+            Self::IncrementalRepack => "--task=incremental-repack",
+            Self::PackRefs => "--task=pack-refs",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitMaintenanceRunRequest {
+    #[serde(flatten)]
+    pub maintenance: GitMaintenanceRequest,
+    /// Tasks to run; git's configured defaults when empty.
+    pub tasks: Option<Vec<GitMaintenanceTask>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitIndexLock {
+    pub path: String,
+    pub exists: bool,
+    /// Milliseconds since the lock file was last modified.
+    pub age_ms: Option<i64>,
+    /// Old, and no git process is running in the repository that could
+    /// still hold it. Never set where processes cannot be inspected.
+    pub stale: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitIndexLockRemoveRequest {
+    pub repository_path: String,
+    /// Remove the lock even when it is not known to be stale.
+    pub force: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCompareRequest {
+    pub repository_path: String,
+    pub base: String,
+    pub head: String,
+    /// Diff `head` against the merge base (`base...head`) rather than
+    /// against `base` itself. Defaults to true.
+    pub merge_base: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCompareResponse {
+    pub base_oid: String,
+    pub head_oid: String,
+    pub merge_base_oid: Option<String>,
+    /// Commits reachable from `head` but not from `base`.
+    pub ahead: Vec<GitGraphEntry>,
+    /// Commits reachable from `base` but not from `head`.
+    pub behind: Vec<GitGraphEntry>,
+    pub files: Vec<GitPatchFileStat>,
+    pub additions: u64,
+    pub deletions: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCompareFileRequest {
+    pub repository_path: String,
+    pub base: String,
+    pub head: String,
+    pub merge_base: Option<bool>,
+    pub path: String,
+    /// Previous path of a renamed file, so the diff shows the rename.
+    pub original_path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitFileDiff {
+    pub path: String,
+    pub binary: bool,
+    /// Unified diff; empty when the file did not change.
+    pub patch: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitRangeDiffRequest {
+    pub repository_path: String,
+    /// Commits before the rewrite, as `base..tip`.
+    pub old_range: String,
+    /// Commits after the rewrite, as `base..tip`.
+    pub new_range: String,
+    pub creation_factor: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitRangeDiffStatus {
+    Unchanged,
+    Changed,
+    /// Only in the old range.
+    Removed,
+    /// Only in the new range.
+    Added,
+}
+
+impl GitRangeDiffStatus {
+    pub fn parse(marker: &str) -> Option<Self> {
+        match marker {
+            "=" => Some(Self::Unchanged),
+            "!" => Some(Self::Changed),
+            "<" => Some(Self::Removed),
+            ">" => Some(Self::Added),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitRangeDiffPair {
+    pub status: GitRangeDiffStatus,
+    pub old_index: Option<u32>,
+    pub old_commit: Option<String>,
+    pub new_index: Option<u32>,
+    pub new_commit: Option<String>,
+    pub subject: String,
+    /// Diff between the two patches, for changed pairs.
+    pub diff: Option<String>,
+}
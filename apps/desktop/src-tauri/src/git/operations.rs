@@ -1,21 +1,72 @@
-use crate::git::{
-    auth::{collect_cleanup, merge_auth_env, GitAuth},
-    models::{
-        GitBranchRequest, GitBranchesResponse, GitCheckoutRequest, GitCommandOutcome,
-        GitCommitDetails, GitCommitDetailsRequest, GitCommitRequest, GitDeleteBranchRequest,
-        GitError, GitErrorResponse, GitGraphResponse, GitLogResponse, GitPathInfo, GitRemoteList,
-        GitRepositoryInfo, GitRepositoryRequest, GitStageRequest, GitStashApplyRequest,
-        GitStashList, GitStashPushRequest, GitStatusResponse, GitStreamRequest, GitSwitchResponse,
+use crate::{
+    db::Database,
+    git::{
+        auth::{self, collect_cleanup, merge_auth_env, prepare_auth, GitAuth},
+        commit_message,
+        credential_server::CredentialServer,
+        hooks,
+        journal::{self, OperationEntry},
+        models::{
+            GitBranchRequest, GitBranchesResponse, GitCheckoutRequest, GitCloneRequest,
+            GitCommandConfig, GitCommandOutcome, GitCommitDetails, GitCommitDetailsRequest,
+            GitCommitLintSeverity, GitCommitRequest, GitDeleteBranchRequest, GitError,
+            GitErrorKind, GitErrorResponse, GitFetchRequest, GitGraphEntry, GitGraphResponse,
+            GitHookAction, GitLogResponse, GitOperationKind, GitPathInfo, GitPullRequest,
+            GitPullStrategy, GitRemoteList, GitRepositoryInfo, GitRepositoryRequest, GitRunStatus,
+            GitStageRequest, GitStashApplyRequest, GitStashList, GitStashPushRequest,
+            GitStatusResponse, GitStreamRequest, GitSwitchResponse, GitSyncRequest,
+            GitSyncResponse,
+        },
+        policy, search,
+        service::GitService,
+        sparse, ssh,
+        streaming::{run_streaming_command, spawn_streaming_command},
+        util,
     },
-    service::GitService,
-    streaming::run_streaming_command,
-    util,
+    git_args,
 };
 use log::{error, info};
-use tauri::{AppHandle, State};
+use std::{collections::HashMap, path::PathBuf, time::Instant};
+use tauri::{AppHandle, Manager, State};
 use tauri_plugin_shell::ShellExt;
+use uuid::Uuid;
 
-async fn run_git_capture(
+const GRAPH_PRETTY_FORMAT: &str = "--pretty=format:%H|%P|%an|%ad|%s";
+
+/// Hosts the credential helper may answer for while git runs `args`; empty
+/// for commands that never contact a remote.
+pub(crate) async fn credential_hosts(
+    app: &AppHandle,
+    config: &GitCommandConfig,
+    args: &[String],
+) -> Vec<String> {
+    if !auth::is_network_command(args) {
+        return Vec::new();
+    }
+    // Exits with 1 when no remote is configured, e.g. before a clone.
+    let output = app
+        .shell()
+        .command(&config.executable.program)
+        .args(config.executable.prefix_args.clone())
+        .args(["config", "--get-regexp", r"^remote\..*\.(push)?url$"])
+        .current_dir(config.working_dir.clone())
+        .output()
+        .await;
+    let stdout = output
+        .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+        .unwrap_or_default();
+    let remotes: Vec<(String, String)> = stdout
+        .lines()
+        .filter_map(|line| {
+            let (key, url) = line.split_once(' ')?;
+            let (name, _) = key.strip_prefix("remote.")?.rsplit_once('.')?;
+            Some((name.to_string(), url.to_string()))
+        })
+        .collect();
+    auth::session_hosts(args, &remotes)
+}
+
+pub(crate) async fn run_git_capture(
     app: &AppHandle,
     service: &GitService,
     repository_path: &str,
@@ -23,6 +74,7 @@ async fn run_git_capture(
     auth: Option<GitAuth>,
 ) -> Result<GitCommandOutcome, GitError> {
     let config = service.prepare(Some(repository_path))?;
+    let hosts = credential_hosts(app, &config, &args).await;
     let display_args = args.clone();
     let mut command = app
         .shell()
@@ -33,11 +85,10 @@ async fn run_git_capture(
 
     let mut cleanup = Vec::new();
     let mut env = std::collections::HashMap::new();
-    if let Some(auth) = auth {
-        let prepared = auth.prepare()?;
-        env = merge_auth_env(env, &prepared.env);
-        cleanup = collect_cleanup(cleanup, prepared.cleanup);
-    }
+    let server = app.state::<CredentialServer>();
+    let prepared = prepare_auth(auth.as_ref(), &server, &hosts)?;
+    env = merge_auth_env(env, &prepared.env);
+    cleanup = collect_cleanup(cleanup, prepared.cleanup);
 
     for (key, value) in env.iter() {
         command = command.env(key, value);
@@ -60,14 +111,43 @@ async fn run_git_capture(
         error!("git:exit code={} {}", code, command_display);
     }
 
+    let error = (!output.status.success()).then(|| {
+        GitErrorResponse::from_stderr(&stderr, output.status.code(), "git command failed")
+    });
+
     Ok(GitCommandOutcome {
         exit_code: output.status.code(),
         success: output.status.success(),
         stdout,
         stderr,
+        error,
     })
 }
 
+/// Top-level directory of the worktree containing `repository_path`.
+pub(crate) async fn repository_root(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+) -> Result<PathBuf, GitErrorResponse> {
+    let outcome = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec!["rev-parse".into(), "--show-toplevel".into()],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to resolve the repository root",
+        ));
+    }
+    Ok(PathBuf::from(outcome.stdout))
+}
+
 #[tauri::command]
 pub fn git_path_info(service: State<'_, GitService>) -> GitPathInfo {
     service.info()
@@ -86,18 +166,21 @@ pub async fn git_detect_repository(
     _app: AppHandle,
     request: GitRepositoryRequest,
 ) -> Result<GitRepositoryInfo, GitErrorResponse> {
-    let path = request.repository_path.ok_or_else(|| GitErrorResponse {
-        message: "repository_path is required".into(),
+    let path = request.repository_path.ok_or_else(|| {
+        GitErrorResponse::new(GitErrorKind::InvalidArgument, "repository_path is required")
     })?;
     let canonical = util::canonicalize_path(&path).map_err(GitErrorResponse::from)?;
     Ok(util::detect_repository(&canonical))
 }
 
+/// Working tree status. The sparse checkout state costs extra git calls, a
+/// full tree listing in cone mode, so it is only read with `include_sparse`.
 #[tauri::command]
 pub async fn git_status(
     app: AppHandle,
     service: State<'_, GitService>,
     repository_path: String,
+    include_sparse: Option<bool>,
 ) -> Result<GitStatusResponse, GitErrorResponse> {
     let outcome = run_git_capture(
         &app,
@@ -114,7 +197,15 @@ pub async fn git_status(
     .await
     .map_err(GitErrorResponse::from)?;
 
-    Ok(util::parse_status(&outcome.stdout))
+    let mut status = util::parse_status(&outcome.stdout);
+    if include_sparse.unwrap_or(false) {
+        // The status itself is still useful when this lookup fails.
+        match sparse::sparse_state(&app, &service, &repository_path).await {
+            Ok(sparse) => status.sparse = Some(sparse).filter(|sparse| sparse.enabled),
+            Err(err) => error!("git:status sparse state unavailable: {}", err.message),
+        }
+    }
+    Ok(status)
 }
 
 #[tauri::command]
@@ -124,9 +215,10 @@ pub async fn git_stage(
     request: GitStageRequest,
 ) -> Result<GitCommandOutcome, GitErrorResponse> {
     if request.paths.is_empty() {
-        return Err(GitErrorResponse {
-            message: "no paths provided".into(),
-        });
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            "no paths provided",
+        ));
     }
 
     let mut args = vec!["add".into(), "--".into()];
@@ -146,9 +238,10 @@ pub async fn git_unstage(
     request: GitStageRequest,
 ) -> Result<GitCommandOutcome, GitErrorResponse> {
     if request.paths.is_empty() {
-        return Err(GitErrorResponse {
-            message: "no paths provided".into(),
-        });
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            "no paths provided",
+        ));
     }
 
     let mut args = vec!["restore".into(), "--staged".into(), "--".into()];
@@ -165,20 +258,80 @@ pub async fn git_unstage(
 pub async fn git_commit(
     app: AppHandle,
     service: State<'_, GitService>,
+    database: State<'_, Database>,
     request: GitCommitRequest,
 ) -> Result<GitCommandOutcome, GitErrorResponse> {
-    if request.message.trim().is_empty() {
-        return Err(GitErrorResponse {
-            message: "commit message cannot be empty".into(),
-        });
+    let cleaned =
+        commit_message::cleaned_message(&app, &service, &request.repository_path, &request.message)
+            .await;
+    if cleaned.unchanged_template {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            "the commit message is unchanged from the template",
+        ));
+    }
+    let message = cleaned.text;
+    if message.trim().is_empty() {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            "commit message cannot be empty",
+        ));
+    }
+
+    let lint_config = if request.skip_lint.unwrap_or(false) {
+        None
+    } else {
+        commit_message::commit_lint_config(&app, &service, &database, &request.repository_path)
+            .await
+    };
+    if let Some(config) = lint_config {
+        let lint = commit_message::lint_with(&message, &config);
+        if !lint.valid {
+            let errors: Vec<String> = lint
+                .warnings
+                .into_iter()
+                .filter(|warning| warning.severity == GitCommitLintSeverity::Error)
+                .map(|warning| warning.message)
+                .collect();
+            return Err(GitErrorResponse::new(
+                GitErrorKind::InvalidArgument,
+                format!("commit message rejected: {}", errors.join("; ")),
+            ));
+        }
     }
 
     let mut args = vec!["commit".into(), "-m".into()];
-    args.push(util::sanitize_arg(&request.message, "message").map_err(GitErrorResponse::from)?);
+    args.push(util::sanitize_arg(&message, "message").map_err(GitErrorResponse::from)?);
 
-    run_git_capture(&app, &service, &request.repository_path, args, None)
+    hooks::announce_hooks(
+        &app,
+        &service,
+        &request.repository_path,
+        GitHookAction::Commit,
+        None,
+    )
+    .await;
+    let before = journal::snapshot_head(&app, &service, &request.repository_path).await;
+    let outcome = run_git_capture(&app, &service, &request.repository_path, args, None)
         .await
-        .map_err(GitErrorResponse::from)
+        .map_err(GitErrorResponse::from)?;
+
+    if outcome.success {
+        let after = journal::snapshot_head(&app, &service, &request.repository_path).await;
+        let subject = message.lines().next().unwrap_or_default();
+        journal::record_head_change(
+            &app,
+            &request.repository_path,
+            GitOperationKind::Commit,
+            before,
+            after,
+            format!("commit \"{subject}\""),
+        )
+        .await;
+        search::schedule_index_update(app.clone(), request.repository_path.clone());
+    }
+
+    Ok(outcome)
 }
 
 #[tauri::command]
@@ -194,7 +347,7 @@ pub async fn git_graph(
         vec![
             "log".into(),
             "--date=iso-strict".into(),
-            "--pretty=format:%H|%P|%an|%ad|%s".into(),
+            GRAPH_PRETTY_FORMAT.into(),
             "-n".into(),
             "200".into(),
         ],
@@ -289,20 +442,37 @@ pub async fn git_switch_branch(
     let branch = util::sanitize_arg(&request.branch, "branch").map_err(GitErrorResponse::from)?;
     args.push(branch.clone());
 
+    hooks::announce_hooks(
+        &app,
+        &service,
+        &request.repository_path,
+        GitHookAction::Checkout,
+        None,
+    )
+    .await;
+    let before = journal::snapshot_head(&app, &service, &request.repository_path).await;
     let outcome = run_git_capture(&app, &service, &request.repository_path, args, None)
         .await
         .map_err(GitErrorResponse::from)?;
 
     if !outcome.success {
-        return Err(GitErrorResponse {
-            message: if outcome.stderr.is_empty() {
-                "failed to switch branch".into()
-            } else {
-                outcome.stderr
-            },
-        });
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to switch branch",
+        ));
     }
 
+    let after = journal::snapshot_head(&app, &service, &request.repository_path).await;
+    journal::record_head_change(
+        &app,
+        &request.repository_path,
+        GitOperationKind::Switch,
+        before,
+        after,
+        format!("switch to {branch}"),
+    )
+    .await;
+
     Ok(GitSwitchResponse { branch })
 }
 
@@ -321,20 +491,34 @@ pub async fn git_delete_branch(
     let branch = util::sanitize_arg(&request.branch, "branch").map_err(GitErrorResponse::from)?;
     args.push(branch.clone());
 
+    let ref_name = format!("refs/heads/{branch}");
+    let tip = journal::resolve_ref(&app, &service, &request.repository_path, &ref_name).await;
     let outcome = run_git_capture(&app, &service, &request.repository_path, args, None)
         .await
         .map_err(GitErrorResponse::from)?;
 
     if !outcome.success {
-        return Err(GitErrorResponse {
-            message: if outcome.stderr.is_empty() {
-                "failed to delete branch".into()
-            } else {
-                outcome.stderr
-            },
-        });
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to delete branch",
+        ));
     }
 
+    journal::record_operation(
+        &app,
+        &request.repository_path,
+        OperationEntry {
+            kind: GitOperationKind::DeleteBranch,
+            ref_name: Some(ref_name),
+            previous_head: None,
+            previous_oid: tip,
+            new_oid: None,
+            description: format!("delete branch {branch}"),
+            reset_mode: None,
+        },
+    )
+    .await;
+
     Ok(GitSwitchResponse { branch })
 }
 
@@ -344,6 +528,15 @@ pub async fn git_checkout(
     service: State<'_, GitService>,
     request: GitCheckoutRequest,
 ) -> Result<GitSwitchResponse, GitErrorResponse> {
+    hooks::announce_hooks(
+        &app,
+        &service,
+        &request.repository_path,
+        GitHookAction::Checkout,
+        None,
+    )
+    .await;
+    let before = journal::snapshot_head(&app, &service, &request.repository_path).await;
     let outcome = run_git_capture(
         &app,
         &service,
@@ -358,15 +551,23 @@ pub async fn git_checkout(
     .map_err(GitErrorResponse::from)?;
 
     if !outcome.success {
-        return Err(GitErrorResponse {
-            message: if outcome.stderr.is_empty() {
-                "failed to checkout target".into()
-            } else {
-                outcome.stderr
-            },
-        });
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to checkout target",
+        ));
     }
 
+    let after = journal::snapshot_head(&app, &service, &request.repository_path).await;
+    journal::record_head_change(
+        &app,
+        &request.repository_path,
+        GitOperationKind::Switch,
+        before,
+        after,
+        format!("checkout {}", request.target),
+    )
+    .await;
+
     Ok(GitSwitchResponse {
         branch: request.target,
     })
@@ -410,9 +611,37 @@ pub async fn git_stash_push(
         args.push(message.clone());
     }
 
-    run_git_capture(&app, &service, &request.repository_path, args, None)
+    let previous =
+        journal::resolve_ref(&app, &service, &request.repository_path, "refs/stash").await;
+    let outcome = run_git_capture(&app, &service, &request.repository_path, args, None)
         .await
-        .map_err(GitErrorResponse::from)
+        .map_err(GitErrorResponse::from)?;
+
+    if outcome.success {
+        let stash =
+            journal::resolve_ref(&app, &service, &request.repository_path, "refs/stash").await;
+        if stash.is_some() && stash != previous {
+            journal::record_operation(
+                &app,
+                &request.repository_path,
+                OperationEntry {
+                    kind: GitOperationKind::Stash,
+                    ref_name: Some("refs/stash".into()),
+                    previous_head: None,
+                    previous_oid: previous,
+                    new_oid: stash,
+                    description: match request.message.as_deref() {
+                        Some(message) => format!("stash \"{message}\""),
+                        None => "stash changes".into(),
+                    },
+                    reset_mode: None,
+                },
+            )
+            .await;
+        }
+    }
+
+    Ok(outcome)
 }
 
 #[tauri::command]
@@ -459,42 +688,314 @@ pub async fn git_remote_list(
 pub async fn git_fetch_all(
     app: AppHandle,
     service: State<'_, GitService>,
-    request: GitStreamRequest,
+    request: GitFetchRequest,
 ) -> Result<crate::git::models::GitCommandHandle, GitErrorResponse> {
-    run_streaming_command(app, service, request, vec!["fetch".into(), "--all".into()]).await
+    let GitFetchRequest {
+        mut stream,
+        prune,
+        tags,
+        refspecs,
+    } = request;
+    let remote = stream.remote.take();
+    let branch = stream.branch.take();
+    let args = fetch_args(
+        remote.as_deref(),
+        branch.as_deref(),
+        prune.unwrap_or(false),
+        tags.unwrap_or(false),
+        refspecs.as_deref().unwrap_or(&[]),
+    )
+    .map_err(GitErrorResponse::from)?;
+
+    let repository_path = stream.repository_path.clone();
+    let (handle, completion) =
+        spawn_streaming_command(app.clone(), service, stream, args, HashMap::new()).await?;
+    tauri::async_runtime::spawn(async move {
+        if completion.await.is_ok_and(|completion| completion.success) {
+            search::schedule_index_update(app, repository_path);
+        }
+    });
+
+    Ok(handle)
+}
+
+/// Clones `url` into `parent_path`, optionally as a partial clone and with
+/// only the root files checked out (`--sparse`).
+#[tauri::command]
+pub async fn git_clone(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitCloneRequest,
+) -> Result<crate::git::models::GitCommandHandle, GitErrorResponse> {
+    let mut args = vec!["clone".into(), "--progress".into()];
+    if let Some(filter) = request.filter {
+        args.push(filter.as_arg().into());
+    }
+    if request.sparse.unwrap_or(false) {
+        args.push("--sparse".into());
+    }
+    if let Some(branch) = request.branch.as_ref() {
+        args.push("--branch".into());
+        args.push(util::sanitize_revision(branch, "branch").map_err(GitErrorResponse::from)?);
+    }
+    args.push("--".into());
+    args.push(util::sanitize_arg(request.url.trim(), "url").map_err(GitErrorResponse::from)?);
+    if let Some(directory) = request.directory.as_ref() {
+        args.push(
+            util::sanitize_arg(directory.trim(), "directory").map_err(GitErrorResponse::from)?,
+        );
+    }
+
+    let stream = GitStreamRequest {
+        repository_path: request.parent_path,
+        remote: None,
+        branch: None,
+        auth: request.auth,
+        command_id: request.command_id,
+    };
+    run_streaming_command(app, service, stream, args).await
+}
+
+/// Whether a pull without an explicit strategy rebases, following
+/// `pull.rebase`.
+async fn pull_rebases(app: &AppHandle, service: &GitService, repository_path: &str) -> bool {
+    let outcome = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec!["config".into(), "--get".into(), "pull.rebase".into()],
+        None,
+    )
+    .await;
+    outcome.is_ok_and(|outcome| {
+        outcome.success && !matches!(outcome.stdout.trim(), "" | "false" | "no" | "off" | "0")
+    })
 }
 
 #[tauri::command]
 pub async fn git_pull(
     app: AppHandle,
     service: State<'_, GitService>,
-    request: GitStreamRequest,
+    mut request: GitPullRequest,
 ) -> Result<crate::git::models::GitCommandHandle, GitErrorResponse> {
-    run_streaming_command(app, service, request, vec!["pull".into()]).await
+    let mut args = vec!["pull".into()];
+    match request.strategy {
+        Some(GitPullStrategy::Merge) => args.push("--no-rebase".into()),
+        Some(GitPullStrategy::Rebase) => args.push("--rebase".into()),
+        Some(GitPullStrategy::FfOnly) => args.push("--ff-only".into()),
+        None => {}
+    }
+    match request.autostash {
+        Some(true) => args.push("--autostash".into()),
+        Some(false) => args.push("--no-autostash".into()),
+        None => {}
+    }
+
+    let rebase = match request.strategy {
+        Some(strategy) => strategy == GitPullStrategy::Rebase,
+        None => pull_rebases(&app, &service, &request.stream.repository_path).await,
+    };
+    let command_id = request
+        .stream
+        .command_id
+        .get_or_insert_with(|| Uuid::new_v4().to_string())
+        .clone();
+    hooks::announce_hooks(
+        &app,
+        &service,
+        &request.stream.repository_path,
+        if rebase {
+            GitHookAction::Rebase
+        } else {
+            GitHookAction::Merge
+        },
+        Some(command_id),
+    )
+    .await;
+
+    if !rebase {
+        return run_streaming_command(app, service, request.stream, args).await;
+    }
+
+    // Journal rebasing pulls once they finish so they can be undone.
+    let repository_path = request.stream.repository_path.clone();
+    let before = journal::snapshot_head(&app, &service, &repository_path).await;
+    let (handle, completion) =
+        spawn_streaming_command(app.clone(), service, request.stream, args, HashMap::new()).await?;
+    tauri::async_runtime::spawn(async move {
+        if !completion.await.is_ok_and(|completion| completion.success) {
+            return;
+        }
+        let service = app.state::<GitService>();
+        let after = journal::snapshot_head(&app, &service, &repository_path).await;
+        let description = match before.symbolic.as_deref() {
+            Some(branch) => format!(
+                "pull --rebase into {}",
+                branch.trim_start_matches("refs/heads/")
+            ),
+            None => "pull --rebase".into(),
+        };
+        journal::record_head_change(
+            &app,
+            &repository_path,
+            GitOperationKind::Rebase,
+            before,
+            after,
+            description,
+        )
+        .await;
+    });
+
+    Ok(handle)
+}
+
+#[tauri::command]
+pub async fn git_sync(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitSyncRequest,
+) -> Result<GitSyncResponse, GitErrorResponse> {
+    let args = fetch_args(
+        request.remote.as_deref(),
+        None,
+        request.prune.unwrap_or(false),
+        false,
+        &[],
+    )
+    .map_err(GitErrorResponse::from)?;
+
+    let fetch = run_git_capture(&app, &service, &request.repository_path, args, request.auth)
+        .await
+        .map_err(GitErrorResponse::from)?;
+
+    if !fetch.success {
+        ssh::report_unknown_host(&app, None, &fetch.stderr).await;
+        return Err(GitErrorResponse::from_outcome(
+            &fetch,
+            "failed to fetch from remote",
+        ));
+    }
+    search::schedule_index_update(app.clone(), request.repository_path.clone());
+
+    let head = run_git_capture(
+        &app,
+        &service,
+        &request.repository_path,
+        vec!["rev-parse".into(), "--abbrev-ref".into(), "HEAD".into()],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    let branch =
+        Some(head.stdout).filter(|name| head.success && !name.is_empty() && name != "HEAD");
+
+    let upstream = run_git_capture(
+        &app,
+        &service,
+        &request.repository_path,
+        vec![
+            "rev-parse".into(),
+            "--abbrev-ref".into(),
+            "--symbolic-full-name".into(),
+            "@{upstream}".into(),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    let upstream = Some(upstream.stdout).filter(|name| upstream.success && !name.is_empty());
+
+    let (incoming, outgoing) = match upstream.as_ref() {
+        Some(_) => (
+            graph_between(
+                &app,
+                &service,
+                &request.repository_path,
+                "HEAD..@{upstream}",
+            )
+            .await?,
+            graph_between(
+                &app,
+                &service,
+                &request.repository_path,
+                "@{upstream}..HEAD",
+            )
+            .await?,
+        ),
+        None => (Vec::new(), Vec::new()),
+    };
+
+    Ok(GitSyncResponse {
+        branch,
+        upstream,
+        fetch,
+        incoming,
+        outgoing,
+    })
 }
 
 #[tauri::command]
 pub async fn git_push(
     app: AppHandle,
     service: State<'_, GitService>,
-    request: GitStreamRequest,
+    mut request: GitStreamRequest,
 ) -> Result<crate::git::models::GitCommandHandle, GitErrorResponse> {
+    // Fix the id up front so the hook notice and the stream share it.
+    let command_id = request
+        .command_id
+        .get_or_insert_with(|| Uuid::new_v4().to_string())
+        .clone();
+    hooks::announce_hooks(
+        &app,
+        &service,
+        &request.repository_path,
+        GitHookAction::Push,
+        Some(command_id),
+    )
+    .await;
     run_streaming_command(app, service, request, vec!["push".into()]).await
 }
 
+/// Runs arbitrary git arguments for the front-end. Calls are checked against
+/// [`git_args::check_run_args`], restricted to registered project roots and
+/// recorded in the audit log whatever their outcome.
 #[tauri::command]
 pub async fn git_run(
     app: AppHandle,
     service: State<'_, GitService>,
+    database: State<'_, Database>,
     cwd: String,
     args: Vec<String>,
 ) -> Result<(i32, String, String), String> {
     let joined = args.join(" ");
-    let config = service.prepare(Some(&cwd)).map_err(|err| {
-        let message = format!("git:err run {} @ {} -> {}", joined, cwd, err);
-        error!("{message}");
-        message
-    })?;
+    let started = Instant::now();
+    let audit = |status, exit_code, message| policy::RunAudit {
+        cwd: &cwd,
+        args: &args,
+        status,
+        exit_code,
+        message,
+        duration_ms: Some(started.elapsed().as_millis() as i64),
+    };
+
+    let checked = match git_args::check_run_args(&args) {
+        Ok(()) => match service.prepare(Some(&cwd)) {
+            Ok(config) => policy::check_project_root(&database, &config.working_dir)
+                .await
+                .map(|()| config),
+            Err(err) => Err(err.to_string()),
+        },
+        Err(reason) => Err(reason),
+    };
+    let config = match checked {
+        Ok(config) => config,
+        Err(reason) => {
+            let message = format!("git:err run {} @ {} -> denied: {}", joined, cwd, reason);
+            error!("{message}");
+            policy::record_run(&database, audit(GitRunStatus::Denied, None, Some(reason))).await;
+            return Err(message);
+        }
+    };
 
     let command = app
         .shell()
@@ -503,11 +1004,19 @@ pub async fn git_run(
         .args(args.clone())
         .current_dir(config.working_dir.clone());
 
-    let output = command.output().await.map_err(|err| {
-        let message = format!("git:err run {} @ {} -> {}", joined, cwd, err);
-        error!("{message}");
-        message
-    })?;
+    let output = match command.output().await {
+        Ok(output) => output,
+        Err(err) => {
+            let message = format!("git:err run {} @ {} -> {}", joined, cwd, err);
+            error!("{message}");
+            policy::record_run(
+                &database,
+                audit(GitRunStatus::Error, None, Some(err.to_string())),
+            )
+            .await;
+            return Err(message);
+        }
+    };
 
     let code = output.status.code().unwrap_or(-1);
     let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
@@ -518,6 +1027,46 @@ pub async fn git_run(
     } else {
         error!("git:exit code={} {}", code, joined);
     }
+    let status = if code == 0 {
+        GitRunStatus::Succeeded
+    } else {
+        GitRunStatus::Failed
+    };
+    policy::record_run(&database, audit(status, Some(code), None)).await;
 
     Ok((code, stdout, stderr))
 }
+
+pub(crate) fn fetch_args(
+    remote: Option<&str>,
+    branch: Option<&str>,
+    prune: bool,
+    tags: bool,
+    refspecs: &[String],
+) -> Result<Vec<String>, GitError> {
+    git_args::fetch_args(remote, branch, prune, tags, refspecs).map_err(GitError::InvalidArgument)
+}
+
+pub(crate) async fn graph_between(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    range: &str,
+) -> Result<Vec<GitGraphEntry>, GitErrorResponse> {
+    let outcome = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec![
+            "log".into(),
+            "--date=iso-strict".into(),
+            GRAPH_PRETTY_FORMAT.into(),
+            range.into(),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+
+    Ok(util::parse_graph(&outcome.stdout).entries)
+}
@@ -0,0 +1,169 @@
+use crate::git::{
+    models::{
+        GitAmAction, GitAmActionRequest, GitAmRequest, GitAmResponse, GitError, GitErrorKind,
+        GitErrorResponse, GitFormatPatchRequest, GitPatchPreview, GitPatchPreviewRequest,
+    },
+    operations::run_git_capture,
+    service::GitService,
+    state::operation_state,
+    util,
+};
+use std::path::Path;
+use tauri::{AppHandle, State};
+
+fn patch_file(path: &str) -> Result<String, GitError> {
+    let path = util::sanitize_arg(path, "patch path")?;
+    if !Path::new(&path).is_file() {
+        return Err(GitError::InvalidPath(format!(
+            "patch file `{path}` does not exist"
+        )));
+    }
+    Ok(path)
+}
+
+#[tauri::command]
+pub async fn git_format_patch(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitFormatPatchRequest,
+) -> Result<Vec<String>, GitErrorResponse> {
+    let range = util::sanitize_revision(&request.range, "range").map_err(GitErrorResponse::from)?;
+    let output_dir = util::sanitize_arg(&request.output_dir, "output directory")
+        .map_err(GitErrorResponse::from)?;
+    if !Path::new(&output_dir).is_dir() {
+        return Err(GitErrorResponse::from(GitError::InvalidPath(format!(
+            "output directory `{output_dir}` does not exist"
+        ))));
+    }
+
+    let mut args = vec!["format-patch".into(), "-o".into(), output_dir];
+    if request.cover_letter.unwrap_or(false) {
+        args.push("--cover-letter".into());
+    }
+    if !range.contains("..") {
+        args.push("-1".into());
+    }
+    args.push(range);
+    args.push("--".into());
+
+    let outcome = run_git_capture(&app, &service, &request.repository_path, args, None)
+        .await
+        .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to export patches",
+        ));
+    }
+
+    Ok(outcome
+        .stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Reports which files a patch touches and whether it applies cleanly,
+/// without changing the working tree.
+#[tauri::command]
+pub async fn git_patch_preview(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitPatchPreviewRequest,
+) -> Result<GitPatchPreview, GitErrorResponse> {
+    let patch = patch_file(&request.patch_path).map_err(GitErrorResponse::from)?;
+
+    let stats = run_git_capture(
+        &app,
+        &service,
+        &request.repository_path,
+        vec![
+            "apply".into(),
+            "--numstat".into(),
+            "--summary".into(),
+            patch.clone(),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !stats.success {
+        return Err(GitErrorResponse::from_outcome(
+            &stats,
+            "failed to read patch",
+        ));
+    }
+
+    let check = run_git_capture(
+        &app,
+        &service,
+        &request.repository_path,
+        vec!["apply".into(), "--check".into(), patch],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+
+    Ok(GitPatchPreview {
+        files: util::parse_patch_stats(&stats.stdout),
+        applies_cleanly: check.success,
+        check_output: check.stderr,
+    })
+}
+
+/// Applies patch or mbox files with `git am`. A failed patch leaves the am
+/// session in progress; the returned state lists the conflicts to resolve.
+#[tauri::command]
+pub async fn git_am(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitAmRequest,
+) -> Result<GitAmResponse, GitErrorResponse> {
+    if request.patch_paths.is_empty() {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            "no patches provided",
+        ));
+    }
+
+    let mut args = vec!["am".into()];
+    if request.three_way.unwrap_or(true) {
+        args.push("--3way".into());
+    }
+    for path in request.patch_paths.iter() {
+        args.push(patch_file(path).map_err(GitErrorResponse::from)?);
+    }
+
+    let outcome = run_git_capture(&app, &service, &request.repository_path, args, None)
+        .await
+        .map_err(GitErrorResponse::from)?;
+    let state = operation_state(&app, &service, &request.repository_path).await?;
+    Ok(GitAmResponse { outcome, state })
+}
+
+#[tauri::command]
+pub async fn git_am_action(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitAmActionRequest,
+) -> Result<GitAmResponse, GitErrorResponse> {
+    let flag = match request.action {
+        GitAmAction::Continue => "--continue",
+        GitAmAction::Skip => "--skip",
+        GitAmAction::Abort => "--abort",
+    };
+
+    let outcome = run_git_capture(
+        &app,
+        &service,
+        &request.repository_path,
+        vec!["am".into(), flag.into()],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    let state = operation_state(&app, &service, &request.repository_path).await?;
+    Ok(GitAmResponse { outcome, state })
+}
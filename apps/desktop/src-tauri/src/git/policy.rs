@@ -0,0 +1,129 @@
+//! Policy for the raw `git_run` command: where the front-end may run git and
+//! the audit log of every call. Which arguments it may pass is decided by
+//! [`crate::git_args`].
+
+use crate::{
+    db::Database,
+    git::models::{GitError, GitErrorResponse, GitRunAuditEntry, GitRunStatus},
+    git::util,
+};
+use log::error;
+use std::path::Path;
+use tauri::State;
+use uuid::Uuid;
+
+const AUDIT_LIST_LIMIT: u32 = 200;
+
+/// Ensures `working_dir` (already canonicalized) lies inside one of the
+/// project roots registered in the `projects` table.
+pub(crate) async fn check_project_root(
+    database: &Database,
+    working_dir: &Path,
+) -> Result<(), String> {
+    let roots: Vec<(String,)> = sqlx::query_as("SELECT path FROM projects")
+        .fetch_all(database.pool())
+        .await
+        .map_err(|err| format!("failed to read project roots: {err}"))?;
+
+    let inside = roots
+        .iter()
+        .any(|(root,)| std::fs::canonicalize(root).is_ok_and(|root| working_dir.starts_with(root)));
+    if inside {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} is not inside a registered project",
+            working_dir.display()
+        ))
+    }
+}
+
+/// Outcome of a `git_run` call as stored in the audit log.
+pub(crate) struct RunAudit<'a> {
+    pub cwd: &'a str,
+    pub args: &'a [String],
+    pub status: GitRunStatus,
+    pub exit_code: Option<i32>,
+    pub message: Option<String>,
+    pub duration_ms: Option<i64>,
+}
+
+pub(crate) async fn record_run(database: &Database, audit: RunAudit<'_>) {
+    let args = match serde_json::to_string(audit.args) {
+        Ok(args) => args,
+        Err(err) => {
+            error!("git:run audit failed: {err}");
+            return;
+        }
+    };
+    let result = sqlx::query(
+        "INSERT INTO git_run_audit (id, cwd, args, status, exit_code, message, duration_ms, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(audit.cwd)
+    .bind(args)
+    .bind(audit.status.as_str())
+    .bind(audit.exit_code)
+    .bind(&audit.message)
+    .bind(audit.duration_ms)
+    .bind(util::now_millis())
+    .execute(database.pool())
+    .await;
+
+    if let Err(err) = result {
+        error!("git:run audit failed: {err}");
+    }
+}
+
+/// `id, cwd, args, status, exit_code, message, duration_ms, created_at`.
+type AuditRow = (
+    String,
+    String,
+    String,
+    String,
+    Option<i32>,
+    Option<String>,
+    Option<i64>,
+    i64,
+);
+
+/// Most recent `git_run` calls, newest first.
+#[tauri::command]
+pub async fn git_run_audit(
+    database: State<'_, Database>,
+    limit: Option<u32>,
+) -> Result<Vec<GitRunAuditEntry>, GitErrorResponse> {
+    let rows: Vec<AuditRow> = sqlx::query_as(
+        "SELECT id, cwd, args, status, exit_code, message, duration_ms, created_at
+         FROM git_run_audit ORDER BY created_at DESC LIMIT ?",
+    )
+    .bind(limit.unwrap_or(AUDIT_LIST_LIMIT).min(AUDIT_LIST_LIMIT))
+    .fetch_all(database.pool())
+    .await
+    .map_err(|err| GitErrorResponse::from(GitError::from(err)))?;
+
+    rows.into_iter()
+        .map(
+            |(id, cwd, args, status, exit_code, message, duration_ms, created_at)| {
+                let args = serde_json::from_str(&args)
+                    .map_err(|e| GitErrorResponse::from(GitError::Parse(e.to_string())))?;
+                let status = GitRunStatus::parse(&status).ok_or_else(|| {
+                    GitErrorResponse::from(GitError::Parse(format!(
+                        "unknown git_run status `{status}`"
+                    )))
+                })?;
+                Ok(GitRunAuditEntry {
+                    id,
+                    cwd,
+                    args,
+                    status,
+                    exit_code,
+                    message,
+                    duration_ms,
+                    created_at,
+                })
+            },
+        )
+        .collect()
+}
@@ -0,0 +1,251 @@
+use crate::{
+    git::{
+        journal::{self, OperationEntry},
+        models::{
+            GitErrorKind, GitErrorResponse, GitFileChange, GitOperationKind, GitResetMode,
+            GitResetPreview, GitResetPreviewRequest, GitResetRequest, GitResetResponse,
+        },
+        operations::{graph_between, repository_root, run_git_capture},
+        service::{GitService, ResetConfirmation},
+        util,
+    },
+    porcelain,
+};
+use std::{
+    collections::{hash_map::DefaultHasher, HashSet},
+    fs,
+    hash::{Hash, Hasher},
+};
+use tauri::{AppHandle, State};
+
+async fn resolve_target(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    target: &str,
+) -> Result<String, GitErrorResponse> {
+    let target = util::sanitize_revision(target, "target").map_err(GitErrorResponse::from)?;
+    journal::resolve_ref(
+        app,
+        service,
+        repository_path,
+        &format!("{target}^{{commit}}"),
+    )
+    .await
+    .ok_or_else(|| {
+        GitErrorResponse::new(
+            GitErrorKind::RefNotFound,
+            format!("`{target}` does not name a commit"),
+        )
+    })
+}
+
+/// Fingerprint of the tracked changes a hard reset would discard: the
+/// porcelain status plus the size and modification time of every changed
+/// file, so further edits to an already modified file are noticed too.
+async fn worktree_fingerprint(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+) -> Result<u64, GitErrorResponse> {
+    let mut args: Vec<String> = porcelain::STATUS_ARGS
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+    args.push("--untracked-files=no".into());
+    let outcome = run_git_capture(app, service, repository_path, args, None)
+        .await
+        .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to read repository status",
+        ));
+    }
+
+    // The branch header is left out: a fetch moving the upstream does not
+    // change what the reset would discard.
+    let mut hasher = DefaultHasher::new();
+    let changed = porcelain::changed_paths(&outcome.stdout);
+    changed.hash(&mut hasher);
+    if !changed.is_empty() {
+        // Porcelain paths are relative to the repository root.
+        let root = repository_root(app, service, repository_path).await?;
+        for entry in changed {
+            let metadata = fs::metadata(root.join(&entry.path)).ok();
+            metadata.as_ref().map(|meta| meta.len()).hash(&mut hasher);
+            metadata
+                .and_then(|meta| meta.modified().ok())
+                .hash(&mut hasher);
+        }
+    }
+    Ok(hasher.finish())
+}
+
+async fn confirmation_for(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    target_oid: String,
+) -> Result<ResetConfirmation, GitErrorResponse> {
+    let working_dir = service
+        .prepare(Some(repository_path))
+        .map_err(GitErrorResponse::from)?
+        .working_dir;
+    Ok(ResetConfirmation {
+        working_dir,
+        head_oid: journal::resolve_ref(app, service, repository_path, "HEAD").await,
+        target_oid,
+        worktree: worktree_fingerprint(app, service, repository_path).await?,
+    })
+}
+
+/// Tracked changes in the index or working tree; untracked files survive a
+/// hard reset and are not reported.
+async fn uncommitted_changes(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+) -> Result<Vec<GitFileChange>, GitErrorResponse> {
+    let outcome = run_git_capture(
+        app,
+        service,
+        repository_path,
+        porcelain::STATUS_ARGS
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect(),
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to read repository status",
+        ));
+    }
+
+    let status = util::parse_status(&outcome.stdout);
+    let mut seen = HashSet::new();
+    Ok(status
+        .conflicts
+        .into_iter()
+        .chain(status.staged)
+        .chain(status.unstaged)
+        .filter(|change| seen.insert(change.path.clone()))
+        .collect())
+}
+
+#[tauri::command]
+pub async fn git_reset_preview(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitResetPreviewRequest,
+) -> Result<GitResetPreview, GitErrorResponse> {
+    let target_oid =
+        resolve_target(&app, &service, &request.repository_path, &request.target).await?;
+    let confirmation =
+        confirmation_for(&app, &service, &request.repository_path, target_oid.clone()).await?;
+
+    let lost_changes = if request.mode == GitResetMode::Hard {
+        uncommitted_changes(&app, &service, &request.repository_path).await?
+    } else {
+        Vec::new()
+    };
+    let dropped_commits = match confirmation.head_oid.as_ref() {
+        Some(head) => {
+            graph_between(
+                &app,
+                &service,
+                &request.repository_path,
+                &format!("{target_oid}..{head}"),
+            )
+            .await?
+        }
+        None => Vec::new(),
+    };
+
+    let current_oid = confirmation.head_oid.clone();
+    let confirmation_token =
+        (request.mode == GitResetMode::Hard).then(|| service.issue_reset_token(confirmation));
+
+    Ok(GitResetPreview {
+        mode: request.mode,
+        current_oid,
+        target_oid,
+        lost_changes,
+        dropped_commits,
+        confirmation_token,
+    })
+}
+
+/// Moves the current branch to `target`. Hard resets must present the token
+/// from [`git_reset_preview`]; the prior HEAD is journaled so the reset can be
+/// undone.
+#[tauri::command]
+pub async fn git_reset(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitResetRequest,
+) -> Result<GitResetResponse, GitErrorResponse> {
+    let target_oid =
+        resolve_target(&app, &service, &request.repository_path, &request.target).await?;
+
+    if request.mode == GitResetMode::Hard {
+        let expected =
+            confirmation_for(&app, &service, &request.repository_path, target_oid.clone()).await?;
+        let confirmed = request
+            .confirmation_token
+            .as_deref()
+            .is_some_and(|token| service.redeem_reset_token(token, &expected));
+        if !confirmed {
+            return Err(GitErrorResponse::new(
+                GitErrorKind::InvalidArgument,
+                "hard reset requires a fresh confirmation token from the reset preview; \
+                 HEAD or the working tree changed since it was issued",
+            ));
+        }
+    }
+
+    let before = journal::snapshot_head(&app, &service, &request.repository_path).await;
+    let outcome = run_git_capture(
+        &app,
+        &service,
+        &request.repository_path,
+        vec![
+            "reset".into(),
+            format!("--{}", request.mode.as_str()),
+            target_oid.clone(),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(&outcome, "failed to reset"));
+    }
+
+    if before.oid.as_ref() != Some(&target_oid) {
+        journal::record_operation(
+            &app,
+            &request.repository_path,
+            OperationEntry {
+                kind: GitOperationKind::Reset,
+                ref_name: before.symbolic.clone().or_else(|| Some("HEAD".into())),
+                previous_head: before.symbolic.clone(),
+                previous_oid: before.oid.clone(),
+                new_oid: Some(target_oid.clone()),
+                description: format!("reset --{} {}", request.mode.as_str(), request.target),
+                reset_mode: Some(request.mode),
+            },
+        )
+        .await;
+    }
+
+    Ok(GitResetResponse {
+        mode: request.mode,
+        previous_oid: before.oid,
+        new_oid: target_oid,
+    })
+}
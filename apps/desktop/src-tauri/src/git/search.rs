@@ -0,0 +1,477 @@
+use crate::{
+    db::Database,
+    git::{
+        journal::repository_key,
+        models::{
+            GitCommitRecord, GitError, GitErrorKind, GitErrorResponse, GitSearchMatch,
+            GitSearchMode, GitSearchRequest, GitSearchResponse,
+        },
+        operations::run_git_capture,
+        service::GitService,
+        util,
+    },
+};
+use log::{error, info};
+use std::collections::HashSet;
+use tauri::{AppHandle, Manager, State};
+use tokio::sync::Mutex;
+
+const DEFAULT_LIMIT: u32 = 50;
+const MAX_LIMIT: u32 = 500;
+
+/// Serializes index updates so concurrent triggers do not insert a commit twice.
+static INDEX_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// `hash, author, email, committed_at, subject, highlighted text, snippet`
+/// of an index match.
+type SearchRow = (String, String, String, i64, String, String, String);
+
+async fn ref_tips(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+) -> Result<Vec<String>, GitError> {
+    let outcome = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec![
+            "for-each-ref".into(),
+            "--format=%(objectname)".into(),
+            "refs/heads".into(),
+            "refs/remotes".into(),
+            "refs/tags".into(),
+        ],
+        None,
+    )
+    .await?;
+    let mut tips: Vec<String> = outcome
+        .stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    let head = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec![
+            "rev-parse".into(),
+            "-q".into(),
+            "--verify".into(),
+            "HEAD".into(),
+        ],
+        None,
+    )
+    .await?;
+    if head.success && !head.stdout.is_empty() {
+        tips.push(head.stdout);
+    }
+
+    let mut seen = HashSet::new();
+    tips.retain(|tip| seen.insert(tip.clone()));
+    tips.sort();
+    Ok(tips)
+}
+
+async fn indexed_tips(database: &Database, key: &str) -> Result<Option<Vec<String>>, GitError> {
+    let row: Option<(String,)> =
+        sqlx::query_as("SELECT indexed_tips FROM git_commit_index_state WHERE repository_path = ?")
+            .bind(key)
+            .fetch_optional(database.pool())
+            .await?;
+    Ok(row.map(|(json,)| serde_json::from_str(&json)).transpose()?)
+}
+
+/// Commits reachable from the previously indexed tips but from none of the
+/// current ones, e.g. rewritten by a rebase or on a deleted branch. `None`
+/// when a previous tip no longer exists, so the index has to be rebuilt.
+async fn removed_commits(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    previous: &[String],
+    tips: &[String],
+) -> Result<Option<Vec<String>>, GitError> {
+    let mut args = vec!["rev-list".into()];
+    args.extend(previous.iter().cloned());
+    args.push("--not".into());
+    args.extend(tips.iter().cloned());
+    args.push("--".into());
+    let outcome = run_git_capture(app, service, repository_path, args, None).await?;
+    if !outcome.success {
+        return Ok(None);
+    }
+    Ok(Some(
+        outcome
+            .stdout
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect(),
+    ))
+}
+
+/// Brings the index in line with the current refs: adds commits reachable
+/// from them but not from the refs seen by the previous update, and drops
+/// commits that are no longer reachable. Returns the number of newly indexed
+/// commits.
+pub(crate) async fn update_index(
+    app: &AppHandle,
+    service: &GitService,
+    database: &Database,
+    repository_path: &str,
+) -> Result<usize, GitError> {
+    let _guard = INDEX_LOCK.lock().await;
+    let key = repository_key(repository_path)?;
+
+    let tips = ref_tips(app, service, repository_path).await?;
+    let mut previous = indexed_tips(database, &key).await?;
+    if previous.as_ref() == Some(&tips) {
+        return Ok(0);
+    }
+
+    // `None` rebuilds the index from scratch.
+    let mut removed = Some(Vec::new());
+    if let Some(old_tips) = previous.as_ref().filter(|tips| !tips.is_empty()) {
+        removed = removed_commits(app, service, repository_path, old_tips, &tips).await?;
+        if removed.is_none() {
+            previous = None;
+        }
+    }
+
+    let mut records = Vec::new();
+    if !tips.is_empty() {
+        let mut args = vec![
+            "log".into(),
+            "--ignore-missing".into(),
+            util::COMMIT_RECORD_FORMAT.into(),
+        ];
+        args.extend(tips.iter().cloned());
+        if let Some(previous) = previous.as_ref().filter(|tips| !tips.is_empty()) {
+            args.push("--not".into());
+            args.extend(previous.iter().cloned());
+        }
+        let outcome = run_git_capture(app, service, repository_path, args, None).await?;
+        if !outcome.success {
+            return Err(GitError::Spawn(outcome.stderr));
+        }
+        records = util::parse_commit_records(&outcome.stdout);
+    }
+
+    let mut tx = database.pool().begin().await?;
+    match removed {
+        Some(hashes) => {
+            for hash in hashes {
+                sqlx::query("DELETE FROM git_commit_index WHERE repository_path = ? AND hash = ?")
+                    .bind(&key)
+                    .bind(hash)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+        None => {
+            sqlx::query("DELETE FROM git_commit_index WHERE repository_path = ?")
+                .bind(&key)
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+    for record in records.iter() {
+        sqlx::query(
+            "INSERT INTO git_commit_index (repository_path, hash, author, email, subject, body, committed_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&key)
+        .bind(&record.hash)
+        .bind(&record.author)
+        .bind(&record.email)
+        .bind(&record.subject)
+        .bind(&record.body)
+        .bind(record.timestamp)
+        .execute(&mut *tx)
+        .await?;
+    }
+    let tips_json = serde_json::to_string(&tips)?;
+    sqlx::query(
+        "INSERT INTO git_commit_index_state (repository_path, indexed_tips, updated_at)
+         VALUES (?, ?, ?)
+         ON CONFLICT(repository_path) DO UPDATE SET
+           indexed_tips = excluded.indexed_tips,
+           updated_at = excluded.updated_at",
+    )
+    .bind(&key)
+    .bind(tips_json)
+    .bind(util::now_millis())
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(records.len())
+}
+
+/// Refreshes the search index in the background, e.g. after a commit or fetch.
+pub(crate) fn schedule_index_update(app: AppHandle, repository_path: String) {
+    tauri::async_runtime::spawn(async move {
+        let service = app.state::<GitService>();
+        let database = app.state::<Database>();
+        match update_index(&app, &service, &database, &repository_path).await {
+            Ok(0) => {}
+            Ok(count) => info!("git:search indexed {count} commits in {repository_path}"),
+            Err(err) => error!("git:search index update failed for {repository_path}: {err}"),
+        }
+    });
+}
+
+fn match_from_row(row: SearchRow, mode: GitSearchMode) -> GitSearchMatch {
+    let (hash, author, email, timestamp, subject, highlighted, snippet) = row;
+    let text = match mode {
+        GitSearchMode::Message if !highlighted.contains('\u{1}') => snippet,
+        _ => highlighted,
+    };
+    let (snippet, highlights) = util::split_highlight_markers(&text);
+    GitSearchMatch {
+        commit: hash,
+        author,
+        email,
+        timestamp,
+        subject,
+        snippet,
+        highlights,
+    }
+}
+
+async fn search_index(
+    database: &Database,
+    key: &str,
+    mode: GitSearchMode,
+    query: &str,
+    limit: u32,
+) -> Result<Vec<GitSearchMatch>, GitError> {
+    let rows: Vec<SearchRow> = match mode {
+        GitSearchMode::Message | GitSearchMode::Author => {
+            let Some(terms) = util::fts_query(query) else {
+                return Ok(Vec::new());
+            };
+            // Author matches show `author <email>` with hits in either part
+            // highlighted, like the `git log` fallback.
+            let (columns, highlighted) = match mode {
+                GitSearchMode::Message => (
+                    "{subject body}",
+                    "highlight(git_commit_index, 4, char(1), char(2))",
+                ),
+                _ => (
+                    "{author email}",
+                    "highlight(git_commit_index, 2, char(1), char(2)) || ' <' || \
+                     highlight(git_commit_index, 3, char(1), char(2)) || '>'",
+                ),
+            };
+            sqlx::query_as(&format!(
+                "SELECT hash, author, email, CAST(committed_at AS INTEGER), subject,
+                        {highlighted},
+                        snippet(git_commit_index, 5, char(1), char(2), '…', 16)
+                 FROM git_commit_index
+                 WHERE git_commit_index MATCH ? AND repository_path = ?
+                 ORDER BY rank LIMIT ?"
+            ))
+            .bind(format!("{columns} : ({terms})"))
+            .bind(key)
+            .bind(limit)
+            .fetch_all(database.pool())
+            .await?
+        }
+        GitSearchMode::Hash => {
+            let prefix = query.to_ascii_lowercase();
+            if !prefix.chars().all(|ch| ch.is_ascii_hexdigit()) {
+                return Ok(Vec::new());
+            }
+            sqlx::query_as(
+                "SELECT hash, author, email, CAST(committed_at AS INTEGER), subject,
+                        char(1) || substr(hash, 1, length(?1)) || char(2) || substr(hash, length(?1) + 1),
+                        ''
+                 FROM git_commit_index
+                 WHERE repository_path = ?2 AND hash LIKE ?1 || '%'
+                 ORDER BY committed_at DESC LIMIT ?3",
+            )
+            .bind(prefix)
+            .bind(key)
+            .bind(limit)
+            .fetch_all(database.pool())
+            .await?
+        }
+        GitSearchMode::Content | GitSearchMode::Regex => return Ok(Vec::new()),
+    };
+
+    Ok(rows
+        .into_iter()
+        .map(|row| match_from_row(row, mode))
+        .collect())
+}
+
+/// First changed line of a patch that contains `query`, or the first changed
+/// line at all when `exact` is false.
+fn changed_line<'a>(patch: &'a str, query: &str, exact: bool) -> Option<&'a str> {
+    let mut changed = patch.lines().filter(|line| {
+        (line.starts_with('+') && !line.starts_with("+++"))
+            || (line.starts_with('-') && !line.starts_with("---"))
+    });
+    if exact {
+        changed.find(|line| line.contains(query))
+    } else {
+        changed.next()
+    }
+    .map(|line| line[1..].trim())
+}
+
+fn match_from_record(record: GitCommitRecord, snippet: &str, query: &str) -> GitSearchMatch {
+    GitSearchMatch {
+        highlights: util::find_highlights(snippet, query),
+        snippet: snippet.to_string(),
+        commit: record.hash,
+        author: record.author,
+        email: record.email,
+        timestamp: record.timestamp,
+        subject: record.subject,
+    }
+}
+
+async fn search_log(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    mode: GitSearchMode,
+    query: &str,
+    limit: u32,
+) -> Result<Vec<GitSearchMatch>, GitErrorResponse> {
+    let mut args = vec![
+        "log".into(),
+        format!("-n{limit}"),
+        util::COMMIT_RECORD_FORMAT.into(),
+    ];
+    let query_arg = util::sanitize_arg(query, "query").map_err(GitErrorResponse::from)?;
+    match mode {
+        GitSearchMode::Message => {
+            args.extend(["--all".into(), "-i".into(), "-F".into()]);
+            args.push(format!("--grep={query_arg}"));
+        }
+        GitSearchMode::Author => {
+            args.extend(["--all".into(), "-i".into(), "-F".into()]);
+            args.push(format!("--author={query_arg}"));
+        }
+        GitSearchMode::Hash => {
+            let prefix =
+                util::sanitize_revision(&query_arg, "hash").map_err(GitErrorResponse::from)?;
+            args.extend(["--no-walk".into(), prefix, "--".into()]);
+        }
+        GitSearchMode::Content | GitSearchMode::Regex => {
+            let flag = if mode == GitSearchMode::Content {
+                "-S"
+            } else {
+                "-G"
+            };
+            args.extend([
+                "--all".into(),
+                format!("{flag}{query_arg}"),
+                "-p".into(),
+                "-U0".into(),
+                "--no-color".into(),
+                "--no-ext-diff".into(),
+            ]);
+        }
+    }
+
+    let outcome = run_git_capture(app, service, repository_path, args, None)
+        .await
+        .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to search history",
+        ));
+    }
+
+    Ok(outcome
+        .stdout
+        .split('\u{1e}')
+        .filter_map(util::parse_commit_record)
+        .map(|(record, patch)| {
+            let snippet = match mode {
+                GitSearchMode::Content => changed_line(patch, query, true),
+                GitSearchMode::Regex => changed_line(patch, query, false),
+                _ => None,
+            }
+            .map(str::to_string)
+            .unwrap_or_else(|| match mode {
+                GitSearchMode::Author => format!("{} <{}>", record.author, record.email),
+                GitSearchMode::Hash => record.hash.clone(),
+                _ => record.subject.clone(),
+            });
+            match_from_record(record, &snippet, query)
+        })
+        .collect())
+}
+
+/// Searches commit history. Message, author and hash lookups are answered
+/// from the SQLite index once it has been built in the background, and from
+/// `git log` before that; content searches run `git log -S`/`-G` directly.
+#[tauri::command]
+pub async fn git_search(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    database: State<'_, Database>,
+    request: GitSearchRequest,
+) -> Result<GitSearchResponse, GitErrorResponse> {
+    let query = request.query.trim();
+    if query.is_empty() {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            "search query cannot be empty",
+        ));
+    }
+    let limit = request.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    if !matches!(request.mode, GitSearchMode::Content | GitSearchMode::Regex) {
+        // Until the first build finishes in the background, searches go
+        // through `git log`.
+        let indexed = async {
+            let key = repository_key(&request.repository_path)?;
+            let built = indexed_tips(&database, &key).await?.is_some();
+            schedule_index_update(app.clone(), request.repository_path.clone());
+            if !built {
+                return Ok(None);
+            }
+            search_index(&database, &key, request.mode, query, limit)
+                .await
+                .map(Some)
+        }
+        .await;
+
+        match indexed {
+            Ok(Some(matches)) => {
+                return Ok(GitSearchResponse {
+                    matches,
+                    from_index: true,
+                })
+            }
+            Ok(None) => {}
+            Err(err) => error!("git:search index unavailable, falling back to git log: {err}"),
+        }
+    }
+
+    let matches = search_log(
+        &app,
+        &service,
+        &request.repository_path,
+        request.mode,
+        query,
+        limit,
+    )
+    .await?;
+    Ok(GitSearchResponse {
+        matches,
+        from_index: false,
+    })
+}
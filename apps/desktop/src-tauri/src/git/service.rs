@@ -1,5 +1,14 @@
 use crate::git::models::{GitCommandConfig, GitError, GitExecutable, GitPathInfo};
-use std::{path::PathBuf, sync::RwLock};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Mutex, RwLock},
+    time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+/// How long a hard reset confirmation token stays valid.
+const RESET_TOKEN_TTL: Duration = Duration::from_secs(300);
 
 #[derive(Debug, Default)]
 struct GitState {
@@ -7,8 +16,19 @@ struct GitState {
     configured: Option<PathBuf>,
 }
 
+/// Repository state a hard reset was previewed against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResetConfirmation {
+    pub working_dir: PathBuf,
+    pub head_oid: Option<String>,
+    pub target_oid: String,
+    /// Fingerprint of the tracked changes the preview showed as lost.
+    pub worktree: u64,
+}
+
 pub struct GitService {
     state: RwLock<GitState>,
+    reset_tokens: Mutex<HashMap<String, (ResetConfirmation, Instant)>>,
 }
 
 impl GitService {
@@ -19,6 +39,27 @@ impl GitService {
                 detected,
                 configured: None,
             }),
+            reset_tokens: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn issue_reset_token(&self, confirmation: ResetConfirmation) -> String {
+        let token = Uuid::new_v4().simple().to_string();
+        let mut tokens = self.reset_tokens.lock().unwrap();
+        tokens.retain(|_, (_, issued)| issued.elapsed() < RESET_TOKEN_TTL);
+        tokens.insert(token.clone(), (confirmation, Instant::now()));
+        token
+    }
+
+    /// Consumes `token`; it is only accepted while the repository is still in
+    /// the state it was issued for.
+    pub fn redeem_reset_token(&self, token: &str, expected: &ResetConfirmation) -> bool {
+        let mut tokens = self.reset_tokens.lock().unwrap();
+        match tokens.remove(token) {
+            Some((confirmation, issued)) => {
+                issued.elapsed() < RESET_TOKEN_TTL && &confirmation == expected
+            }
+            None => false,
         }
     }
 
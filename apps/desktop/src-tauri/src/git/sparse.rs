@@ -0,0 +1,267 @@
+use crate::git::{
+    journal,
+    models::{
+        GitErrorKind, GitErrorResponse, GitSparseCheckoutInitRequest, GitSparseCheckoutSetRequest,
+        GitSparseCheckoutState,
+    },
+    operations::{repository_root, run_git_capture},
+    service::GitService,
+    util,
+};
+use std::{collections::BTreeMap, path::PathBuf, sync::Mutex};
+use tauri::{AppHandle, State};
+
+/// Excluded directories per repository root, with the tree and patterns
+/// they were computed from. Listing every tracked directory is slow in large
+/// repositories and the file tree asks on every refresh.
+static EXCLUDED_CACHE: Mutex<BTreeMap<PathBuf, ExcludedDirectories>> = Mutex::new(BTreeMap::new());
+
+struct ExcludedDirectories {
+    tree: String,
+    patterns: Vec<String>,
+    directories: Vec<String>,
+}
+
+/// Tracked directories of `HEAD` outside the cone `patterns`, reusing the
+/// last result while neither has changed.
+async fn excluded_directories(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    root: &PathBuf,
+    patterns: &[String],
+) -> Result<Vec<String>, GitErrorResponse> {
+    // An unborn HEAD has no tracked directories to exclude.
+    let Some(tree) = journal::resolve_ref(app, service, repository_path, "HEAD^{tree}").await
+    else {
+        return Ok(Vec::new());
+    };
+    if let Some(cached) = EXCLUDED_CACHE.lock().unwrap().get(root) {
+        if cached.tree == tree && cached.patterns == patterns {
+            return Ok(cached.directories.clone());
+        }
+    }
+
+    let listing = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec![
+            "ls-tree".into(),
+            "-r".into(),
+            "-d".into(),
+            "--name-only".into(),
+            "-z".into(),
+            tree.clone(),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !listing.success {
+        return Err(GitErrorResponse::from_outcome(
+            &listing,
+            "failed to list tracked directories",
+        ));
+    }
+    let directories = util::sparse_excluded_directories(&listing.stdout, patterns);
+    EXCLUDED_CACHE.lock().unwrap().insert(
+        root.clone(),
+        ExcludedDirectories {
+            tree,
+            patterns: patterns.to_vec(),
+            directories: directories.clone(),
+        },
+    );
+    Ok(directories)
+}
+
+/// Sparse checkout configuration of the worktree at `repository_path`.
+pub(crate) async fn sparse_state(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+) -> Result<GitSparseCheckoutState, GitErrorResponse> {
+    // Exits with 1 when neither key is set.
+    let config = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec![
+            "config".into(),
+            "--get-regexp".into(),
+            r"^core\.sparsecheckout".into(),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    let (enabled, cone) = util::parse_sparse_config(&config.stdout);
+    if !enabled {
+        return Ok(GitSparseCheckoutState::default());
+    }
+
+    let list = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec!["sparse-checkout".into(), "list".into()],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !list.success {
+        return Err(GitErrorResponse::from_outcome(
+            &list,
+            "failed to list sparse checkout patterns",
+        ));
+    }
+    let patterns: Vec<String> = list
+        .stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    let root = repository_root(app, service, repository_path).await?;
+    let excluded_directories = if cone {
+        excluded_directories(app, service, repository_path, &root, &patterns).await?
+    } else {
+        Vec::new()
+    };
+
+    Ok(GitSparseCheckoutState {
+        enabled,
+        cone,
+        root: Some(root.to_string_lossy().to_string()),
+        patterns,
+        excluded_directories,
+    })
+}
+
+fn sparse_directories(directories: &[String]) -> Result<Vec<String>, GitErrorResponse> {
+    directories
+        .iter()
+        .map(|dir| {
+            let dir = dir.trim().replace('\\', "/");
+            let dir = util::sanitize_arg(dir.trim_matches('/'), "directory")
+                .map_err(GitErrorResponse::from)?;
+            if dir.starts_with('-') {
+                return Err(GitErrorResponse::new(
+                    GitErrorKind::InvalidArgument,
+                    format!("directory may not start with `-`, got `{dir}`"),
+                ));
+            }
+            Ok(dir)
+        })
+        .collect()
+}
+
+async fn run_sparse_checkout(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    args: Vec<String>,
+    failure: &str,
+) -> Result<GitSparseCheckoutState, GitErrorResponse> {
+    let outcome = run_git_capture(app, service, repository_path, args, None)
+        .await
+        .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(&outcome, failure));
+    }
+    sparse_state(app, service, repository_path).await
+}
+
+#[tauri::command]
+pub async fn git_sparse_checkout_list(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    repository_path: String,
+) -> Result<GitSparseCheckoutState, GitErrorResponse> {
+    sparse_state(&app, &service, &repository_path).await
+}
+
+/// Enables sparse checkout, in cone mode unless `cone` is false. Only files
+/// at the repository root stay checked out until directories are added.
+#[tauri::command]
+pub async fn git_sparse_checkout_init(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitSparseCheckoutInitRequest,
+) -> Result<GitSparseCheckoutState, GitErrorResponse> {
+    let mode = if request.cone.unwrap_or(true) {
+        "--cone"
+    } else {
+        "--no-cone"
+    };
+    run_sparse_checkout(
+        &app,
+        &service,
+        &request.repository_path,
+        vec!["sparse-checkout".into(), "init".into(), mode.into()],
+        "failed to initialize sparse checkout",
+    )
+    .await
+}
+
+/// Replaces the sparse set with `directories`.
+#[tauri::command]
+pub async fn git_sparse_checkout_set(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitSparseCheckoutSetRequest,
+) -> Result<GitSparseCheckoutState, GitErrorResponse> {
+    let mut args = vec!["sparse-checkout".into(), "set".into()];
+    args.extend(sparse_directories(&request.directories)?);
+    run_sparse_checkout(
+        &app,
+        &service,
+        &request.repository_path,
+        args,
+        "failed to update sparse checkout",
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn git_sparse_checkout_add(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitSparseCheckoutSetRequest,
+) -> Result<GitSparseCheckoutState, GitErrorResponse> {
+    if request.directories.is_empty() {
+        return Err(GitErrorResponse::new(
+            GitErrorKind::InvalidArgument,
+            "no directories provided",
+        ));
+    }
+    let mut args = vec!["sparse-checkout".into(), "add".into()];
+    args.extend(sparse_directories(&request.directories)?);
+    run_sparse_checkout(
+        &app,
+        &service,
+        &request.repository_path,
+        args,
+        "failed to add sparse checkout directories",
+    )
+    .await
+}
+
+/// Restores the full working tree.
+#[tauri::command]
+pub async fn git_sparse_checkout_disable(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    repository_path: String,
+) -> Result<GitSparseCheckoutState, GitErrorResponse> {
+    run_sparse_checkout(
+        &app,
+        &service,
+        &repository_path,
+        vec!["sparse-checkout".into(), "disable".into()],
+        "failed to disable sparse checkout",
+    )
+    .await
+}
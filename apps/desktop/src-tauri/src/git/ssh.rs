@@ -0,0 +1,218 @@
+use crate::git::{
+    models::{
+        GitError, GitErrorResponse, GitSshAgentInfo, GitSshHostKey, GitSshHostKeyEvent,
+        GitSshKeyFile, GitSshTrustRequest,
+    },
+    util,
+};
+use log::{error, info};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_shell::ShellExt;
+use tempfile::NamedTempFile;
+
+pub const HOST_KEY_EVENT: &str = "git://ssh-host-key";
+
+/// Inspects the stderr of a failed remote command and, when ssh rejected an
+/// unknown host key, scans the host and asks the UI to confirm its fingerprint.
+pub async fn report_unknown_host(app: &AppHandle, command_id: Option<&str>, stderr: &str) {
+    let Some((host, port)) = util::parse_unknown_ssh_host(stderr) else {
+        return;
+    };
+
+    match scan_host_keys(app, &host, port).await {
+        Ok(scanned) => {
+            let payload = GitSshHostKeyEvent {
+                command_id: command_id.map(str::to_string),
+                host,
+                port,
+                keys: scanned.into_iter().map(|(key, _)| key).collect(),
+            };
+            let _ = app.emit(HOST_KEY_EVENT, payload);
+        }
+        Err(err) => error!("git:ssh keyscan failed for {host}: {err}"),
+    }
+}
+
+/// Runs `ssh-keyscan` for `host` and pairs every returned key line with its
+/// fingerprint as reported by `ssh-keygen -l`.
+async fn scan_host_keys(
+    app: &AppHandle,
+    host: &str,
+    port: Option<u16>,
+) -> Result<Vec<(GitSshHostKey, String)>, GitError> {
+    let host = util::sanitize_arg(host, "host")?;
+    if host.starts_with('-') {
+        return Err(GitError::InvalidArgument(format!("invalid host `{host}`")));
+    }
+
+    let mut args = vec!["-T".to_string(), "5".to_string()];
+    if let Some(port) = port {
+        args.push("-p".into());
+        args.push(port.to_string());
+    }
+    args.push(host.clone());
+
+    let output = app
+        .shell()
+        .command("ssh-keyscan")
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| GitError::Spawn(e.to_string()))?;
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let lines: Vec<String> = stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect();
+    if lines.is_empty() {
+        return Err(GitError::Spawn(format!("no host keys returned for {host}")));
+    }
+
+    let mut scanned = NamedTempFile::new()?;
+    for line in lines.iter() {
+        writeln!(scanned, "{line}")?;
+    }
+    scanned.flush()?;
+
+    let output = app
+        .shell()
+        .command("ssh-keygen")
+        .args(["-l", "-f"])
+        .arg(scanned.path())
+        .output()
+        .await
+        .map_err(|e| GitError::Spawn(e.to_string()))?;
+    let fingerprints = util::parse_ssh_fingerprints(&String::from_utf8_lossy(&output.stdout));
+    if fingerprints.len() != lines.len() {
+        return Err(GitError::Parse(
+            "ssh-keygen returned an unexpected number of fingerprints".into(),
+        ));
+    }
+
+    Ok(fingerprints.into_iter().zip(lines).collect())
+}
+
+fn ssh_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".ssh"))
+}
+
+fn append_known_host(line: &str) -> Result<PathBuf, GitError> {
+    let dir = ssh_dir().ok_or_else(|| GitError::InvalidPath("home directory not found".into()))?;
+    if !dir.exists() {
+        fs::create_dir_all(&dir)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&dir, fs::Permissions::from_mode(0o700))?;
+        }
+    }
+
+    let path = dir.join("known_hosts");
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    writeln!(file, "{line}")?;
+    Ok(path)
+}
+
+fn describe_key_file(public_key: &Path) -> Option<GitSshKeyFile> {
+    let private_key = public_key.with_extension("");
+    if !private_key.is_file() {
+        return None;
+    }
+    let contents = fs::read_to_string(public_key).ok()?;
+    let mut parts = contents.split_whitespace();
+    let key_type = parts.next()?.to_string();
+    parts.next()?;
+    let comment = parts.collect::<Vec<&str>>().join(" ");
+
+    Some(GitSshKeyFile {
+        private_key_path: private_key.to_string_lossy().to_string(),
+        public_key_path: public_key.to_string_lossy().to_string(),
+        key_type,
+        comment,
+    })
+}
+
+#[tauri::command]
+pub async fn git_ssh_trust_host(
+    app: AppHandle,
+    request: GitSshTrustRequest,
+) -> Result<String, GitErrorResponse> {
+    let scanned = scan_host_keys(&app, &request.host, request.port)
+        .await
+        .map_err(GitErrorResponse::from)?;
+    let (_, line) = scanned
+        .into_iter()
+        .find(|(key, _)| key.fingerprint == request.fingerprint)
+        .ok_or_else(|| {
+            GitErrorResponse::from(GitError::UntrustedHostKey(format!(
+                "{} (fingerprint changed since it was confirmed)",
+                request.host
+            )))
+        })?;
+
+    let path = append_known_host(&line).map_err(GitErrorResponse::from)?;
+    info!("git:ssh trusted {} {}", request.host, request.fingerprint);
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub async fn git_ssh_agent_info(app: AppHandle) -> Result<GitSshAgentInfo, GitErrorResponse> {
+    let socket = std::env::var("SSH_AUTH_SOCK")
+        .ok()
+        .filter(|value| !value.is_empty());
+
+    let output = match app.shell().command("ssh-add").arg("-l").output().await {
+        Ok(output) => output,
+        Err(_) => {
+            return Ok(GitSshAgentInfo {
+                running: false,
+                socket,
+                keys: Vec::new(),
+            })
+        }
+    };
+
+    // ssh-add exits with 1 when the agent has no identities and 2 when it
+    // cannot reach an agent at all.
+    let running = matches!(output.status.code(), Some(0) | Some(1));
+    let keys = if running {
+        util::parse_ssh_agent_keys(&String::from_utf8_lossy(&output.stdout))
+    } else {
+        Vec::new()
+    };
+
+    Ok(GitSshAgentInfo {
+        running,
+        socket,
+        keys,
+    })
+}
+
+#[tauri::command]
+pub fn git_ssh_list_keys() -> Result<Vec<GitSshKeyFile>, GitErrorResponse> {
+    let Some(dir) = ssh_dir().filter(|dir| dir.is_dir()) else {
+        return Ok(Vec::new());
+    };
+
+    let mut keys: Vec<GitSshKeyFile> = fs::read_dir(&dir)
+        .map_err(|e| GitErrorResponse::from(GitError::from(e)))?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("pub"))
+        .filter_map(|path| describe_key_file(&path))
+        .collect();
+    keys.sort_by(|a, b| a.private_key_path.cmp(&b.private_key_path));
+    Ok(keys)
+}
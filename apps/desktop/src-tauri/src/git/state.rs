@@ -0,0 +1,108 @@
+use crate::git::{
+    models::{GitErrorResponse, GitInProgressOperation, GitOperationState},
+    operations::run_git_capture,
+    service::GitService,
+};
+use std::{fs, path::Path};
+use tauri::{AppHandle, State};
+
+fn read_number(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+fn first_line(path: &Path) -> Option<String> {
+    let contents = fs::read_to_string(path).ok()?;
+    let line = contents.lines().next()?.trim();
+    (!line.is_empty()).then(|| line.to_string())
+}
+
+/// Works out which operation is in progress from the marker files git keeps
+/// in the repository's git directory.
+fn detect_operation(git_dir: &Path, state: &mut GitOperationState) {
+    let rebase_apply = git_dir.join("rebase-apply");
+    let rebase_merge = git_dir.join("rebase-merge");
+
+    if rebase_apply.is_dir() {
+        state.operation = Some(if rebase_apply.join("applying").exists() {
+            GitInProgressOperation::Am
+        } else {
+            GitInProgressOperation::Rebase
+        });
+        state.step = read_number(&rebase_apply.join("next"));
+        state.total_steps = read_number(&rebase_apply.join("last"));
+        state.current_subject = first_line(&rebase_apply.join("final-commit"));
+    } else if rebase_merge.is_dir() {
+        state.operation = Some(GitInProgressOperation::Rebase);
+        state.step = read_number(&rebase_merge.join("msgnum"));
+        state.total_steps = read_number(&rebase_merge.join("end"));
+        state.current_subject = first_line(&rebase_merge.join("message"));
+    } else if git_dir.join("MERGE_HEAD").exists() {
+        state.operation = Some(GitInProgressOperation::Merge);
+        state.current_subject = first_line(&git_dir.join("MERGE_MSG"));
+    } else if git_dir.join("CHERRY_PICK_HEAD").exists() {
+        state.operation = Some(GitInProgressOperation::CherryPick);
+        state.current_subject = first_line(&git_dir.join("MERGE_MSG"));
+    } else if git_dir.join("REVERT_HEAD").exists() {
+        state.operation = Some(GitInProgressOperation::Revert);
+        state.current_subject = first_line(&git_dir.join("MERGE_MSG"));
+    } else if git_dir.join("BISECT_LOG").exists() {
+        state.operation = Some(GitInProgressOperation::Bisect);
+    }
+}
+
+pub(crate) async fn operation_state(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+) -> Result<GitOperationState, GitErrorResponse> {
+    let git_dir = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec!["rev-parse".into(), "--absolute-git-dir".into()],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !git_dir.success {
+        return Err(GitErrorResponse::from_outcome(
+            &git_dir,
+            "failed to locate the git directory",
+        ));
+    }
+
+    let mut state = GitOperationState::default();
+    detect_operation(Path::new(&git_dir.stdout), &mut state);
+
+    let conflicts = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec![
+            "diff".into(),
+            "--name-only".into(),
+            "--diff-filter=U".into(),
+            "-z".into(),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    state.conflicts = conflicts
+        .stdout
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    Ok(state)
+}
+
+#[tauri::command]
+pub async fn git_operation_state(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    repository_path: String,
+) -> Result<GitOperationState, GitErrorResponse> {
+    operation_state(&app, &service, &repository_path).await
+}
@@ -0,0 +1,332 @@
+use crate::{
+    db::Database,
+    git::{
+        journal::repository_key,
+        models::{
+            GitAuthorStats, GitDirectoryOwner, GitDirectoryOwnership, GitError, GitErrorResponse,
+            GitFileChurn, GitNumstatCommit, GitPatchFileStat, GitRepositoryStats, GitStatsRequest,
+            GitStatsWindow,
+        },
+        operations::run_git_capture,
+        service::GitService,
+        util,
+    },
+};
+use log::error;
+use std::collections::{HashMap, HashSet};
+use tauri::{AppHandle, State};
+
+/// Time windows reported on the dashboard, in days; `None` is all history.
+const WINDOWS: [Option<u32>; 5] = [Some(7), Some(30), Some(90), Some(365), None];
+const HOTSPOT_LIMIT: usize = 25;
+const OWNER_LIMIT: usize = 5;
+/// Cached statistics are reused for the same HEAD until the time windows
+/// have drifted by a day.
+const CACHE_MAX_AGE_MS: i64 = 24 * 60 * 60 * 1000;
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Lines changed by a file entry; binary files count as one change so that
+/// touching them still shows up in ownership.
+fn changes(file: &GitPatchFileStat) -> u64 {
+    if file.binary {
+        return 1;
+    }
+    u64::from(file.additions.unwrap_or(0)) + u64::from(file.deletions.unwrap_or(0))
+}
+
+fn directory_of(path: &str) -> &str {
+    path.split_once('/').map(|(dir, _)| dir).unwrap_or("")
+}
+
+#[derive(Default)]
+struct FileAccumulator {
+    commits: u32,
+    additions: u64,
+    deletions: u64,
+    authors: HashSet<String>,
+}
+
+/// Aggregates commits (newest first, as `git log` lists them).
+fn summarize(commits: &[GitNumstatCommit], head_oid: String, now: i64) -> GitRepositoryStats {
+    let mut authors: Vec<GitAuthorStats> = Vec::new();
+    let mut author_index: HashMap<String, usize> = HashMap::new();
+    let mut windows: Vec<(GitStatsWindow, HashSet<String>)> = WINDOWS
+        .iter()
+        .map(|days| {
+            (
+                GitStatsWindow {
+                    days: *days,
+                    commits: 0,
+                    additions: 0,
+                    deletions: 0,
+                    active_authors: 0,
+                },
+                HashSet::new(),
+            )
+        })
+        .collect();
+    let mut files: HashMap<&str, FileAccumulator> = HashMap::new();
+    let mut directories: HashMap<&str, HashMap<String, u64>> = HashMap::new();
+
+    for commit in commits {
+        let key = commit.email.to_lowercase();
+        let additions: u64 = commit
+            .files
+            .iter()
+            .map(|file| u64::from(file.additions.unwrap_or(0)))
+            .sum();
+        let deletions: u64 = commit
+            .files
+            .iter()
+            .map(|file| u64::from(file.deletions.unwrap_or(0)))
+            .sum();
+
+        let idx = *author_index.entry(key.clone()).or_insert_with(|| {
+            authors.push(GitAuthorStats {
+                name: commit.author.clone(),
+                email: commit.email.clone(),
+                commits: 0,
+                additions: 0,
+                deletions: 0,
+                first_commit_at: commit.timestamp,
+                last_commit_at: commit.timestamp,
+            });
+            authors.len() - 1
+        });
+        let author = &mut authors[idx];
+        author.commits += 1;
+        author.additions += additions;
+        author.deletions += deletions;
+        author.first_commit_at = author.first_commit_at.min(commit.timestamp);
+        author.last_commit_at = author.last_commit_at.max(commit.timestamp);
+
+        for (window, active) in windows.iter_mut() {
+            let inside = window
+                .days
+                .is_none_or(|days| commit.timestamp >= now - i64::from(days) * DAY_MS);
+            if inside {
+                window.commits += 1;
+                window.additions += additions;
+                window.deletions += deletions;
+                active.insert(key.clone());
+            }
+        }
+
+        for file in commit.files.iter() {
+            let entry = files.entry(file.path.as_str()).or_default();
+            entry.commits += 1;
+            entry.additions += u64::from(file.additions.unwrap_or(0));
+            entry.deletions += u64::from(file.deletions.unwrap_or(0));
+            entry.authors.insert(key.clone());
+
+            *directories
+                .entry(directory_of(&file.path))
+                .or_default()
+                .entry(key.clone())
+                .or_default() += changes(file);
+        }
+    }
+
+    let mut hotspots: Vec<GitFileChurn> = files
+        .into_iter()
+        .map(|(path, acc)| GitFileChurn {
+            path: path.to_string(),
+            commits: acc.commits,
+            additions: acc.additions,
+            deletions: acc.deletions,
+            authors: acc.authors.len() as u32,
+        })
+        .collect();
+    hotspots.sort_by(|a, b| {
+        b.commits
+            .cmp(&a.commits)
+            .then((b.additions + b.deletions).cmp(&(a.additions + a.deletions)))
+            .then(a.path.cmp(&b.path))
+    });
+    hotspots.truncate(HOTSPOT_LIMIT);
+
+    let mut directories: Vec<GitDirectoryOwnership> = directories
+        .into_iter()
+        .map(|(path, by_author)| {
+            let total: u64 = by_author.values().sum();
+            let mut ranked: Vec<(String, u64)> = by_author.into_iter().collect();
+            ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+            let mut covered = 0;
+            let mut bus_factor = 0;
+            for (_, count) in ranked.iter() {
+                if covered * 2 > total {
+                    break;
+                }
+                covered += count;
+                bus_factor += 1;
+            }
+
+            let owners = ranked
+                .into_iter()
+                .take(OWNER_LIMIT)
+                .map(|(key, count)| {
+                    let author = &authors[author_index[&key]];
+                    GitDirectoryOwner {
+                        name: author.name.clone(),
+                        email: author.email.clone(),
+                        changes: count,
+                        share: if total == 0 {
+                            0.0
+                        } else {
+                            count as f64 / total as f64
+                        },
+                    }
+                })
+                .collect();
+
+            GitDirectoryOwnership {
+                path: path.to_string(),
+                bus_factor,
+                changes: total,
+                owners,
+            }
+        })
+        .collect();
+    directories.sort_by(|a, b| b.changes.cmp(&a.changes).then(a.path.cmp(&b.path)));
+
+    authors.sort_by(|a, b| b.commits.cmp(&a.commits).then(a.name.cmp(&b.name)));
+
+    GitRepositoryStats {
+        head_oid,
+        computed_at: now,
+        total_commits: commits.len() as u32,
+        authors,
+        windows: windows
+            .into_iter()
+            .map(|(mut window, active)| {
+                window.active_authors = active.len() as u32;
+                window
+            })
+            .collect(),
+        hotspots,
+        directories,
+        cached: false,
+    }
+}
+
+async fn cached_stats(
+    database: &Database,
+    key: &str,
+    head_oid: &str,
+) -> Result<Option<GitRepositoryStats>, GitError> {
+    let row: Option<(String, i64)> = sqlx::query_as(
+        "SELECT stats, computed_at FROM git_repository_stats
+         WHERE repository_path = ? AND head_oid = ?",
+    )
+    .bind(key)
+    .bind(head_oid)
+    .fetch_optional(database.pool())
+    .await?;
+
+    match row {
+        Some((json, computed_at)) if util::now_millis() - computed_at < CACHE_MAX_AGE_MS => {
+            serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| GitError::Parse(e.to_string()))
+        }
+        _ => Ok(None),
+    }
+}
+
+async fn store_stats(
+    database: &Database,
+    key: &str,
+    stats: &GitRepositoryStats,
+) -> Result<(), GitError> {
+    let json = serde_json::to_string(stats).map_err(|e| GitError::Parse(e.to_string()))?;
+    sqlx::query(
+        "INSERT INTO git_repository_stats (repository_path, head_oid, stats, computed_at)
+         VALUES (?, ?, ?, ?)
+         ON CONFLICT(repository_path) DO UPDATE SET
+           head_oid = excluded.head_oid,
+           stats = excluded.stats,
+           computed_at = excluded.computed_at",
+    )
+    .bind(key)
+    .bind(&stats.head_oid)
+    .bind(json)
+    .bind(stats.computed_at)
+    .execute(database.pool())
+    .await?;
+    Ok(())
+}
+
+/// Contributor and churn statistics for the history reachable from HEAD,
+/// excluding merge commits. Results are cached per HEAD.
+#[tauri::command]
+pub async fn git_repository_stats(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    database: State<'_, Database>,
+    request: GitStatsRequest,
+) -> Result<GitRepositoryStats, GitErrorResponse> {
+    let head = run_git_capture(
+        &app,
+        &service,
+        &request.repository_path,
+        vec![
+            "rev-parse".into(),
+            "-q".into(),
+            "--verify".into(),
+            "HEAD".into(),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !head.success || head.stdout.is_empty() {
+        // Nothing committed yet.
+        return Ok(summarize(&[], String::new(), util::now_millis()));
+    }
+    let head_oid = head.stdout;
+
+    let key = repository_key(&request.repository_path).map_err(GitErrorResponse::from)?;
+    if !request.refresh.unwrap_or(false) {
+        match cached_stats(&database, &key, &head_oid).await {
+            Ok(Some(mut stats)) => {
+                stats.cached = true;
+                return Ok(stats);
+            }
+            Ok(None) => {}
+            Err(err) => error!("git:stats cache read failed: {err}"),
+        }
+    }
+
+    let outcome = run_git_capture(
+        &app,
+        &service,
+        &request.repository_path,
+        vec![
+            "log".into(),
+            "--no-merges".into(),
+            "--numstat".into(),
+            "-M".into(),
+            "--no-color".into(),
+            util::NUMSTAT_LOG_FORMAT.into(),
+            head_oid.clone(),
+            "--".into(),
+        ],
+        None,
+    )
+    .await
+    .map_err(GitErrorResponse::from)?;
+    if !outcome.success {
+        return Err(GitErrorResponse::from_outcome(
+            &outcome,
+            "failed to read history",
+        ));
+    }
+
+    let commits = util::parse_numstat_log(&outcome.stdout);
+    let stats = summarize(&commits, head_oid, util::now_millis());
+    if let Err(err) = store_stats(&database, &key, &stats).await {
+        error!("git:stats cache write failed: {err}");
+    }
+    Ok(stats)
+}
@@ -1,16 +1,19 @@
 use crate::git::{
-    auth::{collect_cleanup, merge_auth_env},
+    auth::{collect_cleanup, merge_auth_env, prepare_auth},
+    credential_server::CredentialServer,
     models::{
-        GitCommandHandle, GitError, GitErrorResponse, GitStreamEvent, GitStreamEventKind,
-        GitStreamRequest,
+        GitCommandCompletion, GitCommandHandle, GitError, GitErrorResponse, GitStreamEvent,
+        GitStreamEventKind, GitStreamRequest,
     },
+    operations::credential_hosts,
     service::GitService,
-    util,
+    ssh, util,
 };
 use std::collections::HashMap;
 use tauri::Emitter;
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager, State};
 use tauri_plugin_shell::{process::CommandEvent, ShellExt};
+use tokio::sync::oneshot;
 use uuid::Uuid;
 
 pub const STREAM_EVENT: &str = "git://stream";
@@ -19,18 +22,34 @@ pub async fn run_streaming_command(
     app: AppHandle,
     service: State<'_, GitService>,
     request: GitStreamRequest,
-    mut args: Vec<String>,
+    args: Vec<String>,
 ) -> Result<GitCommandHandle, GitErrorResponse> {
+    let (handle, _completion) =
+        spawn_streaming_command(app, service, request, args, HashMap::new()).await?;
+    Ok(handle)
+}
+
+/// Starts a streamed git command with extra environment variables for the
+/// child process. The returned receiver resolves once the command exits, for
+/// callers that need to act on the result.
+pub async fn spawn_streaming_command(
+    app: AppHandle,
+    service: State<'_, GitService>,
+    request: GitStreamRequest,
+    mut args: Vec<String>,
+    extra_env: HashMap<String, String>,
+) -> Result<(GitCommandHandle, oneshot::Receiver<GitCommandCompletion>), GitErrorResponse> {
     if let Some(remote) = request.remote.as_ref() {
-        args.push(util::sanitize_arg(remote, "remote").map_err(GitErrorResponse::from)?);
+        args.push(util::sanitize_revision(remote, "remote").map_err(GitErrorResponse::from)?);
     }
     if let Some(branch) = request.branch.as_ref() {
-        args.push(util::sanitize_arg(branch, "branch").map_err(GitErrorResponse::from)?);
+        args.push(util::sanitize_revision(branch, "branch").map_err(GitErrorResponse::from)?);
     }
 
     let config = service
         .prepare(Some(&request.repository_path))
         .map_err(GitErrorResponse::from)?;
+    let hosts = credential_hosts(&app, &config, &args).await;
 
     let mut command = app
         .shell()
@@ -40,13 +59,13 @@ pub async fn run_streaming_command(
         .current_dir(config.working_dir.clone());
 
     let mut cleanup = Vec::new();
-    let mut env: HashMap<String, String> = HashMap::new();
+    let mut env = extra_env;
 
-    if let Some(auth) = request.auth {
-        let prepared = auth.prepare().map_err(GitErrorResponse::from)?;
-        env = merge_auth_env(env, &prepared.env);
-        cleanup = collect_cleanup(cleanup, prepared.cleanup);
-    }
+    let server = app.state::<CredentialServer>();
+    let prepared =
+        prepare_auth(request.auth.as_ref(), &server, &hosts).map_err(GitErrorResponse::from)?;
+    env = merge_auth_env(env, &prepared.env);
+    cleanup = collect_cleanup(cleanup, prepared.cleanup);
 
     for (key, value) in env.iter() {
         command = command.env(key, value);
@@ -62,9 +81,12 @@ pub async fn run_streaming_command(
     let event_name = STREAM_EVENT.to_string();
     let app_handle = app.clone();
     let stream_command_id = command_id.clone();
+    let (completion_tx, completion_rx) = oneshot::channel();
 
     tauri::async_runtime::spawn(async move {
         let _cleanup_guard = cleanup;
+        let mut completion_tx = Some(completion_tx);
+        let mut stderr = String::new();
         while let Some(event) = rx.recv().await {
             match event {
                 CommandEvent::Stdout(line) => {
@@ -74,27 +96,49 @@ pub async fn run_streaming_command(
                         data: Some(String::from_utf8_lossy(&line).to_string()),
                         exit_code: None,
                         success: None,
+                        error: None,
                     };
                     let _ = app_handle.emit(&event_name, payload);
                 }
                 CommandEvent::Stderr(line) => {
+                    let data = String::from_utf8_lossy(&line).to_string();
+                    stderr.push_str(&data);
                     let payload = GitStreamEvent {
                         command_id: stream_command_id.clone(),
                         kind: GitStreamEventKind::Stderr,
-                        data: Some(String::from_utf8_lossy(&line).to_string()),
+                        data: Some(data),
                         exit_code: None,
                         success: None,
+                        error: None,
                     };
                     let _ = app_handle.emit(&event_name, payload);
                 }
                 CommandEvent::Terminated(payload) => {
                     let success = payload.code.unwrap_or(-1) == 0;
+                    if !success {
+                        ssh::report_unknown_host(&app_handle, Some(&stream_command_id), &stderr)
+                            .await;
+                    }
+                    if let Some(tx) = completion_tx.take() {
+                        let _ = tx.send(GitCommandCompletion {
+                            command_id: stream_command_id.clone(),
+                            exit_code: payload.code,
+                            success,
+                        });
+                    }
                     let payload = GitStreamEvent {
                         command_id: stream_command_id.clone(),
                         kind: GitStreamEventKind::Completed,
                         data: None,
                         exit_code: payload.code,
                         success: Some(success),
+                        error: (!success).then(|| {
+                            GitErrorResponse::from_stderr(
+                                &stderr,
+                                payload.code,
+                                "git command failed",
+                            )
+                        }),
                     };
                     let _ = app_handle.emit(&event_name, payload);
                 }
@@ -105,6 +149,7 @@ pub async fn run_streaming_command(
                         data: Some(message),
                         exit_code: None,
                         success: None,
+                        error: None,
                     };
                     let _ = app_handle.emit(&event_name, payload);
                 }
@@ -113,5 +158,5 @@ pub async fn run_streaming_command(
         }
     });
 
-    Ok(GitCommandHandle { command_id })
+    Ok((GitCommandHandle { command_id }, completion_rx))
 }
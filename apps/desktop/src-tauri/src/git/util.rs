@@ -1,12 +1,19 @@
 use crate::git::models::{
-    GitBranchesResponse, GitCommitDetails, GitCommitFileChange, GitError, GitFileChange,
-    GitGraphEntry, GitGraphResponse, GitLogEntry, GitLogResponse, GitRemote, GitRemoteList,
-    GitRepositoryInfo, GitStashEntry, GitStashList, GitStatusResponse,
+    GitBisectStatus, GitBranchesResponse, GitCommitDetails, GitCommitFileChange, GitCommitFooter,
+    GitCommitLintConfig, GitCommitLintSeverity, GitCommitLintWarning, GitCommitRecord,
+    GitConfigEntry, GitConfigScope, GitConventionalCommit, GitError, GitErrorDetails, GitErrorKind,
+    GitFileAttributes, GitFileChange, GitGraphEntry, GitGraphResponse, GitIgnoreMatch,
+    GitLargeBlob, GitLfsFile, GitLfsLock, GitLfsPattern, GitLfsPointer, GitLogEntry,
+    GitLogResponse, GitNumstatCommit, GitObjectCounts, GitPatchFileStat, GitRangeDiffPair,
+    GitRangeDiffStatus, GitReflogEntry, GitRemote, GitRemoteList, GitRepositoryInfo,
+    GitSearchHighlight, GitSshAgentKey, GitSshHostKey, GitStashEntry, GitStashList,
+    GitStatusResponse,
 };
 use std::{
     collections::HashSet,
     fs,
     path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 pub fn sanitize_arg(value: &str, field: &str) -> Result<String, GitError> {
@@ -23,6 +30,24 @@ pub fn sanitize_arg(value: &str, field: &str) -> Result<String, GitError> {
     Ok(value.to_string())
 }
 
+/// Like [`sanitize_arg`], but also rejects values git would parse as options.
+pub fn sanitize_revision(value: &str, field: &str) -> Result<String, GitError> {
+    let value = sanitize_arg(value.trim(), field)?;
+    if value.starts_with('-') {
+        return Err(GitError::InvalidArgument(format!(
+            "{field} must be a revision, got `{value}`"
+        )));
+    }
+    Ok(value)
+}
+
+pub fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
 pub fn canonicalize_path(path: &str) -> Result<PathBuf, GitError> {
     if path.trim().is_empty() {
         return Err(GitError::InvalidPath("path cannot be empty".into()));
@@ -40,6 +65,55 @@ pub fn canonicalize_path(path: &str) -> Result<PathBuf, GitError> {
     fs::canonicalize(dir.clone()).map_err(|_| GitError::InvalidPath("path does not exist".into()))
 }
 
+/// Joins a repository-relative `path` onto `root`, rejecting absolute paths
+/// and `..` components so the result cannot leave the repository.
+pub fn repository_file(root: &Path, path: &str) -> Result<PathBuf, GitError> {
+    let relative = Path::new(path.trim());
+    if path.trim().is_empty()
+        || relative.is_absolute()
+        || relative.components().any(|part| {
+            !matches!(
+                part,
+                std::path::Component::Normal(_) | std::path::Component::CurDir
+            )
+        })
+    {
+        return Err(GitError::InvalidPath(format!(
+            "`{path}` is not a path inside the repository"
+        )));
+    }
+    Ok(root.join(relative))
+}
+
+/// Extracts the lowercase `host[:port]` part of a remote URL, accepting both
+/// URL syntax (`https://user@host/repo`) and scp-like syntax (`git@host:repo`).
+pub fn remote_host(url: &str) -> Option<String> {
+    let trimmed = url.trim();
+    let authority = if let Some((_, rest)) = trimmed.split_once("://") {
+        rest.split('/').next().unwrap_or("")
+    } else if let Some((prefix, rest)) = trimmed.split_once(':') {
+        if prefix.contains('/') || prefix.contains('\\') || prefix.len() == 1 {
+            return None;
+        }
+        if !rest.is_empty() && rest.chars().all(|c| c.is_ascii_digit()) {
+            trimmed
+        } else {
+            prefix
+        }
+    } else if !trimmed.contains('/') && !trimmed.contains('\\') {
+        trimmed
+    } else {
+        return None;
+    };
+
+    let host = authority.rsplit('@').next().unwrap_or("").trim();
+    if host.is_empty() {
+        None
+    } else {
+        Some(host.to_ascii_lowercase())
+    }
+}
+
 pub fn detect_repository(path: &Path) -> GitRepositoryInfo {
     let mut current = if path.is_dir() {
         path.to_path_buf()
@@ -191,6 +265,7 @@ pub fn parse_status(output: &str) -> GitStatusResponse {
         conflicts,
         untracked,
         is_clean,
+        sparse: None,
     }
 }
 
@@ -426,3 +501,1227 @@ pub fn parse_commit_details(output: &str) -> GitCommitDetails {
         files,
     }
 }
+
+/// Finds the host that ssh refused because its key is not in `known_hosts`.
+pub fn parse_unknown_ssh_host(stderr: &str) -> Option<(String, Option<u16>)> {
+    let candidate = stderr.lines().find_map(|line| {
+        if let Some(idx) = line.find("host key is known for ") {
+            let rest = &line[idx + "host key is known for ".len()..];
+            return rest.split_whitespace().next().map(str::to_string);
+        }
+        if let Some(rest) = line.strip_prefix("The authenticity of host '") {
+            return rest.split(['\'', ' ']).next().map(str::to_string);
+        }
+        None
+    })?;
+
+    if let Some(bracketed) = candidate.strip_prefix('[') {
+        let (host, port) = bracketed.split_once("]:")?;
+        return Some((host.to_string(), port.parse().ok()));
+    }
+    Some((candidate, None))
+}
+
+/// Parses `ssh-keygen -l` and `ssh-add -l` lines (`bits fingerprint comment (TYPE)`).
+fn parse_key_listing(line: &str) -> Option<(Option<u32>, String, String, String)> {
+    let trimmed = line.trim();
+    let mut parts = trimmed.splitn(3, ' ');
+    let bits = parts.next()?.parse::<u32>().ok();
+    let fingerprint = parts.next()?.to_string();
+    let rest = parts.next().unwrap_or("").trim();
+    let (comment, key_type) = match rest.rfind(" (") {
+        Some(idx) if rest.ends_with(')') => (&rest[..idx], &rest[idx + 2..rest.len() - 1]),
+        _ => (rest, ""),
+    };
+    Some((bits, fingerprint, comment.to_string(), key_type.to_string()))
+}
+
+pub fn parse_ssh_fingerprints(output: &str) -> Vec<GitSshHostKey> {
+    output
+        .lines()
+        .filter_map(parse_key_listing)
+        .map(|(_, fingerprint, _, key_type)| GitSshHostKey {
+            key_type,
+            fingerprint,
+        })
+        .collect()
+}
+
+pub fn parse_ssh_agent_keys(output: &str) -> Vec<GitSshAgentKey> {
+    output
+        .lines()
+        .filter_map(parse_key_listing)
+        .filter(|(_, fingerprint, _, _)| fingerprint.contains(':'))
+        .map(|(bits, fingerprint, comment, key_type)| GitSshAgentKey {
+            bits,
+            fingerprint,
+            comment,
+            key_type,
+        })
+        .collect()
+}
+
+/// Maps git's stderr onto an error kind, collecting the paths or lock file
+/// the message refers to.
+pub fn classify_stderr(stderr: &str) -> (GitErrorKind, GitErrorDetails) {
+    let lower = stderr.to_ascii_lowercase();
+    let mut details = GitErrorDetails::default();
+
+    if let Some(lock_path) = stderr
+        .lines()
+        .find(|line| line.contains(".lock': File exists"))
+        .and_then(|line| line.split('\'').nth(1))
+    {
+        details.lock_path = Some(lock_path.to_string());
+        return (GitErrorKind::LockContention, details);
+    }
+    if lower.contains("another git process seems to be running") {
+        return (GitErrorKind::LockContention, details);
+    }
+
+    if lower.contains("host key verification failed") {
+        return (GitErrorKind::HostKeyVerification, details);
+    }
+
+    // Checked before the network patterns: HTTP 401/403 responses also
+    // produce "unable to access".
+    const AUTH_PATTERNS: [&str; 11] = [
+        "authentication failed",
+        "returned error: 401",
+        "returned error: 403",
+        "401 unauthorized",
+        "403 forbidden",
+        "could not read username",
+        "could not read password",
+        "permission denied (publickey",
+        "invalid username or password",
+        "http basic: access denied",
+        "terminal prompts disabled",
+    ];
+    if AUTH_PATTERNS.iter().any(|pattern| lower.contains(pattern)) {
+        return (GitErrorKind::AuthenticationFailed, details);
+    }
+
+    let conflicts: Vec<String> = stderr
+        .lines()
+        .filter(|line| line.starts_with("CONFLICT ("))
+        .filter_map(|line| {
+            line.rsplit_once(" in ")
+                .map(|(_, path)| path.trim().to_string())
+        })
+        .collect();
+    if !conflicts.is_empty()
+        || lower.contains("automatic merge failed")
+        || lower.contains("you have unmerged paths")
+        || lower.contains("needs merge")
+    {
+        details.paths = conflicts;
+        return (GitErrorKind::MergeConflict, details);
+    }
+
+    if lower.contains("would be overwritten by")
+        || lower.contains("you have unstaged changes")
+        || lower.contains("please commit your changes or stash them")
+        || lower.contains("your index contains uncommitted changes")
+    {
+        details.paths = stderr
+            .lines()
+            .filter(|line| line.starts_with('\t'))
+            .map(|line| line.trim().to_string())
+            .collect();
+        return (GitErrorKind::DirtyWorktree, details);
+    }
+
+    if lower.contains("has no upstream branch")
+        || lower.contains("no tracking information")
+        || lower.contains("no upstream configured")
+    {
+        return (GitErrorKind::NoUpstream, details);
+    }
+
+    if lower.contains("non-fast-forward")
+        || lower.contains("[rejected]")
+        || lower.contains("updates were rejected")
+    {
+        return (GitErrorKind::NonFastForward, details);
+    }
+
+    if lower.contains("is not fully merged") {
+        return (GitErrorKind::BranchNotMerged, details);
+    }
+
+    if lower.contains("did not match any file(s) known to git")
+        || lower.contains("unknown revision")
+        || lower.contains("invalid reference")
+        || lower.contains("not a valid object name")
+        || lower.contains("couldn't find remote ref")
+    {
+        return (GitErrorKind::RefNotFound, details);
+    }
+
+    if lower.contains("not a git repository") {
+        return (GitErrorKind::NotARepository, details);
+    }
+
+    const NETWORK_PATTERNS: [&str; 5] = [
+        "could not resolve host",
+        "connection timed out",
+        "connection refused",
+        "failed to connect",
+        "unable to access",
+    ];
+    if NETWORK_PATTERNS
+        .iter()
+        .any(|pattern| lower.contains(pattern))
+    {
+        return (GitErrorKind::Network, details);
+    }
+
+    (GitErrorKind::Unknown, details)
+}
+
+/// Parses `git bisect log`. The comment lines carry resolved hashes, so they
+/// are used instead of the replayable `git bisect ...` commands.
+pub fn parse_bisect_log(output: &str) -> GitBisectStatus {
+    let mut status = GitBisectStatus {
+        active: true,
+        ..GitBisectStatus::default()
+    };
+
+    for line in output.lines() {
+        let Some(rest) = line.strip_prefix("# ") else {
+            continue;
+        };
+        let Some((label, entry)) = rest.split_once(": [") else {
+            continue;
+        };
+        let Some((hash, _)) = entry.split_once(']') else {
+            continue;
+        };
+        let hash = hash.to_string();
+        match label {
+            "bad" => status.bad = Some(hash),
+            "good" => status.good.push(hash),
+            "skip" => status.skipped.push(hash),
+            "first bad commit" => status.first_bad_commit = Some(hash),
+            _ => {}
+        }
+    }
+
+    status
+}
+
+/// Reads `bisect_nr` and `bisect_steps` from `git rev-list --bisect-vars`.
+pub fn parse_bisect_vars(output: &str) -> Option<(u32, u32)> {
+    let mut remaining = None;
+    let mut steps = None;
+    for line in output.lines() {
+        let Some((key, value)) = line.trim().split_once('=') else {
+            continue;
+        };
+        let value = value.trim_matches('\'').parse::<u32>().ok();
+        match key {
+            "bisect_nr" => remaining = value,
+            "bisect_steps" => steps = value,
+            _ => {}
+        }
+    }
+    Some((remaining?, steps?))
+}
+
+/// Parses `git reflog show --date=unix --format=%H%x09%gd%x09%gs`, newest
+/// first. An entry's old hash is the new hash of the entry after it, so the
+/// last entry of a truncated listing has none.
+pub fn parse_reflog(output: &str) -> Vec<GitReflogEntry> {
+    let rows: Vec<(String, String, i64, String)> = output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            let hash = parts.next()?.trim().to_string();
+            let selector = parts.next()?;
+            let subject = parts.next().unwrap_or("").to_string();
+            let (name, date) = selector.rsplit_once("@{")?;
+            let timestamp = date.trim_end_matches('}').parse::<i64>().ok()? * 1000;
+            if hash.is_empty() {
+                return None;
+            }
+            Some((hash, name.to_string(), timestamp, subject))
+        })
+        .collect();
+
+    rows.iter()
+        .enumerate()
+        .map(|(index, (hash, name, timestamp, subject))| {
+            let (action, message) = subject
+                .split_once(": ")
+                .map(|(action, message)| (action.to_string(), message.to_string()))
+                .unwrap_or_else(|| (subject.clone(), String::new()));
+            GitReflogEntry {
+                selector: format!("{name}@{{{index}}}"),
+                index,
+                old_hash: rows.get(index + 1).map(|(old, ..)| old.clone()),
+                new_hash: hash.clone(),
+                action,
+                message,
+                timestamp: *timestamp,
+            }
+        })
+        .collect()
+}
+
+/// Parses `git apply --numstat --summary`: numstat lines first, followed by
+/// summary lines (`create mode ...`, `rename a => b (90%)`, ...) that are
+/// attached to the file they describe. Files touched by several patches of a
+/// series are merged.
+pub fn parse_patch_stats(output: &str) -> Vec<GitPatchFileStat> {
+    let mut files: Vec<GitPatchFileStat> = Vec::new();
+
+    for line in output.lines() {
+        if let Some(summary) = line.strip_prefix(' ') {
+            let summary = summary.trim_start();
+            let Some(path) = summary_path(summary) else {
+                continue;
+            };
+            if let Some(file) = files.iter_mut().find(|file| file.path == path) {
+                file.summary = Some(match file.summary.take() {
+                    Some(existing) => format!("{existing}; {summary}"),
+                    None => summary.to_string(),
+                });
+            }
+            continue;
+        }
+
+        let mut parts = line.splitn(3, '\t');
+        let (Some(added), Some(deleted), Some(path)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let binary = added == "-" && deleted == "-";
+        let additions = added.parse::<u32>().ok();
+        let deletions = deleted.parse::<u32>().ok();
+
+        match files.iter_mut().find(|file| file.path == path) {
+            Some(file) => {
+                file.binary |= binary;
+                file.additions = sum_counts(file.additions, additions);
+                file.deletions = sum_counts(file.deletions, deletions);
+            }
+            None => files.push(GitPatchFileStat {
+                path: path.to_string(),
+                additions,
+                deletions,
+                binary,
+                summary: None,
+            }),
+        }
+    }
+
+    files
+}
+
+fn sum_counts(a: Option<u32>, b: Option<u32>) -> Option<u32> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a + b),
+        (a, b) => a.or(b),
+    }
+}
+
+/// The file a `--summary` line refers to; renames and copies resolve to the
+/// destination, including the `dir/{old => new}` form.
+fn summary_path(summary: &str) -> Option<String> {
+    let without_similarity = |rest: &'_ str| match rest.rfind(" (") {
+        Some(idx) => rest[..idx].to_string(),
+        None => rest.to_string(),
+    };
+
+    if let Some(moved) = summary
+        .strip_prefix("rename ")
+        .or_else(|| summary.strip_prefix("copy "))
+    {
+        return rename_destination(&without_similarity(moved));
+    }
+    // `create mode 100644 <path>` and `delete mode 100644 <path>`; the path
+    // is everything after the mode and may contain spaces.
+    if let Some(rest) = summary
+        .strip_prefix("create mode ")
+        .or_else(|| summary.strip_prefix("delete mode "))
+    {
+        return without_mode(rest);
+    }
+    // `mode change 100644 => 100755 <path>`
+    if let Some(rest) = summary.strip_prefix("mode change ") {
+        let (_, rest) = rest.split_once(" => ")?;
+        return without_mode(rest);
+    }
+    // `rewrite <path> (75%)`
+    summary.strip_prefix("rewrite ").map(without_similarity)
+}
+
+/// The path following an octal file mode and a single space.
+fn without_mode(rest: &str) -> Option<String> {
+    let (mode, path) = rest.split_once(' ')?;
+    (!mode.is_empty() && mode.chars().all(|c| c.is_ascii_digit()) && !path.is_empty())
+        .then(|| path.to_string())
+}
+
+/// Destination of a `old => new` or `dir/{old => new}/file` rename.
+fn rename_destination(moved: &str) -> Option<String> {
+    if let (Some(open), Some(close)) = (moved.find('{'), moved.rfind('}')) {
+        let (_, new) = moved[open + 1..close].split_once(" => ")?;
+        let path = format!("{}{}{}", &moved[..open], new, &moved[close + 1..]);
+        return Some(path.replace("//", "/"));
+    }
+    moved.split_once(" => ").map(|(_, new)| new.to_string())
+}
+
+/// Parses `git diff --numstat --summary -M`. Renamed files are reported
+/// under their new path.
+pub fn parse_diff_stats(output: &str) -> Vec<GitPatchFileStat> {
+    let normalized: Vec<String> = output
+        .lines()
+        .map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(added), Some(deleted), Some(path)) if path.contains(" => ") => {
+                    match rename_destination(path) {
+                        Some(path) => format!("{added}\t{deleted}\t{path}"),
+                        None => line.to_string(),
+                    }
+                }
+                _ => line.to_string(),
+            }
+        })
+        .collect();
+    parse_patch_stats(&normalized.join("\n"))
+}
+
+/// `git log` format read by [`parse_numstat_log`]; combine with `--numstat`.
+pub const NUMSTAT_LOG_FORMAT: &str = "--format=%x1e%h%x1f%an%x1f%ae%x1f%at";
+
+/// Parses `git log --numstat` output written with [`NUMSTAT_LOG_FORMAT`].
+/// Renamed files are attributed to their new path.
+pub fn parse_numstat_log(output: &str) -> Vec<GitNumstatCommit> {
+    output
+        .split('\u{1e}')
+        .filter_map(|record| {
+            let (header, stats) = record.split_once('\n').unwrap_or((record, ""));
+            let mut fields = header.splitn(4, '\u{1f}');
+            if fields.next()?.trim().is_empty() {
+                return None;
+            }
+            let author = fields.next()?.to_string();
+            let email = fields.next()?.to_string();
+            let timestamp = fields.next()?.trim().parse::<i64>().ok()? * 1000;
+
+            let mut files = parse_patch_stats(stats);
+            for file in files.iter_mut() {
+                if file.path.contains(" => ") {
+                    if let Some(path) = rename_destination(&file.path) {
+                        file.path = path;
+                    }
+                }
+            }
+
+            Some(GitNumstatCommit {
+                author,
+                email,
+                timestamp,
+                files,
+            })
+        })
+        .collect()
+}
+
+/// `git log` format read by [`parse_commit_record`]: records start with
+/// `0x1e`, fields are separated by `0x1f` and the body ends with `0x1d`, so
+/// any patch output that follows can be told apart.
+pub const COMMIT_RECORD_FORMAT: &str = "--format=%x1e%H%x1f%an%x1f%ae%x1f%at%x1f%s%x1f%b%x1d";
+
+/// Parses one `0x1e`-separated record, returning it with the text that
+/// followed the body (the patch when `-p` was given).
+pub fn parse_commit_record(record: &str) -> Option<(GitCommitRecord, &str)> {
+    let mut fields = record.splitn(6, '\u{1f}');
+    let hash = fields.next()?.trim().to_string();
+    if hash.is_empty() {
+        return None;
+    }
+    let author = fields.next()?.to_string();
+    let email = fields.next()?.to_string();
+    let timestamp = fields.next()?.trim().parse::<i64>().ok()? * 1000;
+    let subject = fields.next()?.to_string();
+    let (body, rest) = fields.next()?.split_once('\u{1d}')?;
+    Some((
+        GitCommitRecord {
+            hash,
+            author,
+            email,
+            timestamp,
+            subject,
+            body: body.trim().to_string(),
+        },
+        rest,
+    ))
+}
+
+pub fn parse_commit_records(output: &str) -> Vec<GitCommitRecord> {
+    output
+        .split('\u{1e}')
+        .filter_map(parse_commit_record)
+        .map(|(record, _)| record)
+        .collect()
+}
+
+/// Splits the `\u{1}`/`\u{2}` markers emitted by SQLite's `highlight()` and
+/// `snippet()` out of `text`, returning the plain text and the marked ranges
+/// as character offsets.
+pub fn split_highlight_markers(text: &str) -> (String, Vec<GitSearchHighlight>) {
+    let mut plain = String::with_capacity(text.len());
+    let mut highlights = Vec::new();
+    let mut start = None;
+    let mut offset = 0;
+
+    for ch in text.chars() {
+        match ch {
+            '\u{1}' => start = Some(offset),
+            '\u{2}' => {
+                if let Some(start) = start.take() {
+                    highlights.push(GitSearchHighlight { start, end: offset });
+                }
+            }
+            _ => {
+                plain.push(ch);
+                offset += 1;
+            }
+        }
+    }
+
+    (plain, highlights)
+}
+
+/// Case-insensitive occurrences of `needle` in `text`, as character offsets.
+pub fn find_highlights(text: &str, needle: &str) -> Vec<GitSearchHighlight> {
+    let needle: Vec<char> = needle.to_lowercase().chars().collect();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let haystack: Vec<char> = text.to_lowercase().chars().collect();
+    if haystack.len() != text.chars().count() {
+        // Lowercasing changed the length; offsets would not line up.
+        return Vec::new();
+    }
+
+    let mut highlights = Vec::new();
+    let mut idx = 0;
+    while idx + needle.len() <= haystack.len() {
+        if haystack[idx..idx + needle.len()] == needle[..] {
+            highlights.push(GitSearchHighlight {
+                start: idx,
+                end: idx + needle.len(),
+            });
+            idx += needle.len();
+        } else {
+            idx += 1;
+        }
+    }
+    highlights
+}
+
+/// Turns free text into an FTS5 query: every term is quoted so user input
+/// cannot use query syntax, and the last term matches as a prefix.
+pub fn fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+    if terms.is_empty() {
+        return None;
+    }
+    Some(format!("{}*", terms.join(" ")))
+}
+
+/// Reads `(core.sparseCheckout, core.sparseCheckoutCone)` from
+/// `git config --get-regexp` output. Cone mode only counts when sparse
+/// checkout is enabled.
+pub fn parse_sparse_config(output: &str) -> (bool, bool) {
+    let mut enabled = false;
+    let mut cone = false;
+    for line in output.lines() {
+        let (key, value) = line.split_once(' ').unwrap_or((line, "true"));
+        let value = matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "true" | "yes" | "on" | "1"
+        );
+        match key.to_ascii_lowercase().as_str() {
+            "core.sparsecheckout" => enabled = value,
+            "core.sparsecheckoutcone" => cone = value,
+            _ => {}
+        }
+    }
+    (enabled, enabled && cone)
+}
+
+/// Picks the tracked directories (`git ls-tree -r -d --name-only -z` output)
+/// that a cone-mode sparse checkout of `included` leaves out, keeping only
+/// the outermost excluded directory of each subtree.
+pub fn sparse_excluded_directories(tracked: &str, included: &[String]) -> Vec<String> {
+    let included: Vec<&str> = included
+        .iter()
+        .map(|dir| dir.trim_matches('/'))
+        .filter(|dir| !dir.is_empty())
+        .collect();
+    let recursive = |dir: &str| {
+        included.iter().any(|inc| {
+            dir == *inc || (dir.starts_with(inc) && dir.as_bytes().get(inc.len()) == Some(&b'/'))
+        })
+    };
+    let parent_of_included = |dir: &str| {
+        included
+            .iter()
+            .any(|inc| inc.starts_with(dir) && inc.as_bytes().get(dir.len()) == Some(&b'/'))
+    };
+
+    tracked
+        .split('\0')
+        .map(|dir| dir.trim_matches('\n'))
+        .filter(|dir| !dir.is_empty())
+        .filter(|dir| {
+            if recursive(dir) || parent_of_included(dir) {
+                return false;
+            }
+            match dir.rsplit_once('/') {
+                Some((parent, _)) => parent_of_included(parent),
+                None => true,
+            }
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Version reported by `git lfs version`, e.g. `git-lfs/3.4.0 (GitHub; ...)`.
+pub fn parse_lfs_version(output: &str) -> Option<String> {
+    let first = output.lines().next()?.trim();
+    let version = first.strip_prefix("git-lfs/")?;
+    version.split_whitespace().next().map(str::to_string)
+}
+
+/// `filter=lfs` patterns declared by a `.gitattributes` file at `source`.
+pub fn parse_lfs_attributes(content: &str, source: &str) -> Vec<GitLfsPattern> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let pattern = parts.next()?;
+            let attributes: Vec<&str> = parts.collect();
+            if !attributes.contains(&"filter=lfs") {
+                return None;
+            }
+            Some(GitLfsPattern {
+                pattern: pattern.to_string(),
+                source: source.to_string(),
+                lockable: attributes.contains(&"lockable"),
+            })
+        })
+        .collect()
+}
+
+/// Parses `git lfs ls-files --long`: `<oid> <*|-> <path>`, where `-` marks
+/// files whose content has not been downloaded.
+pub fn parse_lfs_files(output: &str) -> Vec<GitLfsFile> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, ' ');
+            let oid = parts.next()?.trim();
+            let marker = parts.next()?;
+            let path = parts.next()?.trim();
+            if oid.is_empty() || path.is_empty() {
+                return None;
+            }
+            Some(GitLfsFile {
+                path: path.to_string(),
+                oid: oid.to_string(),
+                downloaded: marker == "*",
+            })
+        })
+        .collect()
+}
+
+/// Recognizes an LFS pointer file:
+///
+/// ```text
+/// version https://git-lfs.github.com/spec/v1
+/// oid sha256:4d7a2146...
+/// size 12345
+/// ```
+pub fn parse_lfs_pointer(content: &str) -> Option<GitLfsPointer> {
+    let mut lines = content.lines();
+    if !lines
+        .next()?
+        .starts_with("version https://git-lfs.github.com/spec/")
+    {
+        return None;
+    }
+    let mut oid = None;
+    let mut size = None;
+    for line in lines {
+        if let Some(value) = line.strip_prefix("oid sha256:") {
+            oid = Some(value.trim().to_string());
+        } else if let Some(value) = line.strip_prefix("size ") {
+            size = value.trim().parse::<u64>().ok();
+        }
+    }
+    Some(GitLfsPointer {
+        oid: oid?,
+        size: size?,
+    })
+}
+
+#[derive(serde::Deserialize)]
+struct RawLfsLockOwner {
+    name: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct RawLfsLock {
+    id: String,
+    path: String,
+    owner: Option<RawLfsLockOwner>,
+    locked_at: Option<String>,
+}
+
+impl From<RawLfsLock> for GitLfsLock {
+    fn from(raw: RawLfsLock) -> Self {
+        GitLfsLock {
+            id: raw.id,
+            path: raw.path,
+            owner: raw.owner.and_then(|owner| owner.name),
+            locked_at: raw.locked_at,
+        }
+    }
+}
+
+/// Parses `git lfs locks --json`.
+pub fn parse_lfs_locks(output: &str) -> Result<Vec<GitLfsLock>, GitError> {
+    if output.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let raw: Vec<RawLfsLock> =
+        serde_json::from_str(output).map_err(|e| GitError::Parse(e.to_string()))?;
+    Ok(raw.into_iter().map(GitLfsLock::from).collect())
+}
+
+/// Parses the single lock printed by `git lfs lock --json`.
+pub fn parse_lfs_lock(output: &str) -> Result<GitLfsLock, GitError> {
+    serde_json::from_str::<RawLfsLock>(output)
+        .map(GitLfsLock::from)
+        .map_err(|e| GitError::Parse(e.to_string()))
+}
+
+/// Parses `git check-ignore -v -n`: `source:line:pattern<TAB>path` per
+/// path, with empty fields (`::<TAB>path`) for paths no pattern matched. A
+/// matching negated pattern (`!foo`) means the path is not ignored.
+pub fn parse_check_ignore(output: &str) -> Vec<GitIgnoreMatch> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (rule, path) = line.split_once('\t')?;
+            if path.is_empty() {
+                return None;
+            }
+            // The source may itself contain `:` (e.g. `C:/...`), so look for
+            // the `:<line>:` separator instead of splitting blindly.
+            let (source, line, pattern) = rule
+                .match_indices(':')
+                .find_map(|(idx, _)| {
+                    let rest = &rule[idx + 1..];
+                    let (number, pattern) = rest.split_once(':')?;
+                    let number = number.parse::<u32>().ok()?;
+                    Some((&rule[..idx], Some(number), pattern))
+                })
+                .unwrap_or(("", None, ""));
+            let pattern = Some(pattern).filter(|pattern| !pattern.is_empty());
+            Some(GitIgnoreMatch {
+                path: path.to_string(),
+                ignored: pattern.is_some_and(|pattern| !pattern.starts_with('!')),
+                source: Some(source)
+                    .filter(|source| !source.is_empty())
+                    .map(str::to_string),
+                line,
+                pattern: pattern.map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+/// Parses `git check-attr -a -z`: `path NUL attribute NUL value NUL` for
+/// every specified attribute, grouped by path in output order.
+pub fn parse_check_attr(output: &str) -> Vec<GitFileAttributes> {
+    let fields: Vec<&str> = output.split('\0').collect();
+    let mut files: Vec<GitFileAttributes> = Vec::new();
+
+    for chunk in fields.chunks_exact(3) {
+        let (path, name, value) = (chunk[0], chunk[1], chunk[2]);
+        if path.is_empty() || name.is_empty() {
+            continue;
+        }
+        if files.last().is_none_or(|file| file.path != path) {
+            files.push(GitFileAttributes {
+                path: path.to_string(),
+                ..Default::default()
+            });
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+        file.attributes.insert(name.to_string(), value.to_string());
+        match name {
+            "text" => file.text = Some(value.to_string()),
+            "eol" => file.eol = Some(value.to_string()),
+            "diff" => file.diff = Some(value.to_string()),
+            "merge" => file.merge = Some(value.to_string()),
+            "binary" => file.binary |= value == "set",
+            "filter" => file.lfs = value == "lfs",
+            "lockable" => file.lockable = value == "set",
+            _ => {}
+        }
+    }
+
+    for file in files.iter_mut() {
+        // `-text -diff` without the macro still means git treats it as binary.
+        file.binary |=
+            file.text.as_deref() == Some("unset") && file.diff.as_deref() == Some("unset");
+    }
+    files
+}
+
+/// Parses `git config --list --show-scope --show-origin -z`: `scope NUL
+/// origin NUL key LF value NUL` per entry, lowest precedence first. Keys
+/// without a value are implicit booleans.
+pub fn parse_config_list(output: &str) -> Vec<GitConfigEntry> {
+    let fields: Vec<&str> = output.split('\0').collect();
+    fields
+        .chunks_exact(3)
+        .filter_map(|chunk| {
+            let scope = GitConfigScope::parse(chunk[0])?;
+            let (key, value) = chunk[2].split_once('\n').unwrap_or((chunk[2], "true"));
+            if key.is_empty() {
+                return None;
+            }
+            Some(GitConfigEntry {
+                key: key.to_string(),
+                value: value.to_string(),
+                scope,
+                origin: chunk[1].to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Checks that `key` looks like `section.name` or `section.subsection.name`.
+pub fn sanitize_config_key(key: &str) -> Result<String, GitError> {
+    let key = sanitize_arg(key.trim(), "key")?;
+    let valid = !key.starts_with('-')
+        && !key.contains(['\n', '\r'])
+        && key.split_once('.').zip(key.rsplit_once('.')).is_some_and(
+            |((section, _), (_, name))| {
+                !section.is_empty()
+                    && !name.is_empty()
+                    && section
+                        .chars()
+                        .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+                    && name
+                        .chars()
+                        .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+            },
+        );
+    if !valid {
+        return Err(GitError::InvalidArgument(format!(
+            "`{key}` is not a valid config key"
+        )));
+    }
+    Ok(key)
+}
+
+/// Applies git's `strip` cleanup: drops `#` comment lines and trailing
+/// whitespace, collapses runs of blank lines and trims blank lines at either
+/// end.
+pub fn strip_commit_message(message: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    for line in message.lines() {
+        if line.starts_with('#') {
+            continue;
+        }
+        let line = line.trim_end();
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+const BREAKING_CHANGE: &str = "BREAKING CHANGE";
+
+/// Splits a footer line into token and value: `Token: value`,
+/// `Token #value` or `BREAKING CHANGE: value`.
+fn parse_commit_footer(line: &str) -> Option<GitCommitFooter> {
+    let (token, value) = if let Some(rest) = line.strip_prefix(BREAKING_CHANGE) {
+        (BREAKING_CHANGE, rest.strip_prefix(':')?)
+    } else {
+        line.split_once(": ")
+            .or_else(|| line.split_once(" #"))
+            .filter(|(token, _)| {
+                !token.is_empty()
+                    && token
+                        .chars()
+                        .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+            })?
+    };
+    Some(GitCommitFooter {
+        token: token.to_string(),
+        value: value.trim().to_string(),
+    })
+}
+
+/// Parses a message of the form `type(scope)!: description`, followed by an
+/// optional body and footers. Returns `None` when the header does not match.
+pub fn parse_conventional_commit(message: &str) -> Option<GitConventionalCommit> {
+    let mut lines = message.lines();
+    let header = lines.next()?;
+    let (prefix, description) = header.split_once(':')?;
+    let (prefix, bang) = match prefix.strip_suffix('!') {
+        Some(prefix) => (prefix, true),
+        None => (prefix, false),
+    };
+    let (kind, scope) = match prefix.split_once('(') {
+        Some((kind, rest)) => (kind, Some(rest.strip_suffix(')')?)),
+        None => (prefix, None),
+    };
+    if kind.is_empty() || !kind.chars().all(|ch| ch.is_ascii_alphabetic()) {
+        return None;
+    }
+    if scope.is_some_and(|scope| scope.is_empty() || scope.contains(['(', ')'])) {
+        return None;
+    }
+
+    let rest: Vec<&str> = lines.collect();
+    let paragraphs: Vec<String> = rest
+        .join("\n")
+        .split("\n\n")
+        .map(|paragraph| paragraph.trim_matches('\n').to_string())
+        .filter(|paragraph| !paragraph.trim().is_empty())
+        .collect();
+
+    // Footers form the last paragraph; lines indented with whitespace
+    // continue the previous footer.
+    let mut footers: Vec<GitCommitFooter> = Vec::new();
+    let mut body_paragraphs = paragraphs.len();
+    if let Some(last) = paragraphs.last() {
+        let mut parsed = Vec::new();
+        let mut all_footers = true;
+        for line in last.lines() {
+            if let Some(footer) = parse_commit_footer(line) {
+                parsed.push(footer);
+            } else if let Some(footer) = parsed
+                .last_mut()
+                .filter(|_| line.starts_with(char::is_whitespace))
+            {
+                footer.value.push('\n');
+                footer.value.push_str(line.trim());
+            } else {
+                all_footers = false;
+                break;
+            }
+        }
+        if all_footers && !parsed.is_empty() {
+            footers = parsed;
+            body_paragraphs -= 1;
+        }
+    }
+    let body = Some(paragraphs[..body_paragraphs].join("\n\n")).filter(|body| !body.is_empty());
+
+    Some(GitConventionalCommit {
+        kind: kind.to_string(),
+        scope: scope.map(str::to_string),
+        breaking: bang
+            || footers
+                .iter()
+                .any(|footer| footer.token == BREAKING_CHANGE || footer.token == "BREAKING-CHANGE"),
+        description: description.trim().to_string(),
+        body,
+        footers,
+    })
+}
+
+fn lint_warning(
+    rule: &'static str,
+    severity: GitCommitLintSeverity,
+    message: String,
+    line: Option<u32>,
+) -> GitCommitLintWarning {
+    GitCommitLintWarning {
+        rule,
+        severity,
+        message,
+        line,
+    }
+}
+
+/// Checks a (stripped) commit message against the conventional-commit rules
+/// in `config`.
+pub fn lint_commit_message(
+    message: &str,
+    config: &GitCommitLintConfig,
+) -> (Option<GitConventionalCommit>, Vec<GitCommitLintWarning>) {
+    use GitCommitLintSeverity::{Error, Warning};
+
+    let mut warnings = Vec::new();
+    let header = message.lines().next().unwrap_or_default();
+    if header.trim().is_empty() {
+        warnings.push(lint_warning(
+            "header-empty",
+            Error,
+            "the commit message has no subject line".into(),
+            Some(1),
+        ));
+        return (None, warnings);
+    }
+
+    let Some(parsed) = parse_conventional_commit(message) else {
+        warnings.push(lint_warning(
+            "header-format",
+            Error,
+            "the subject must look like `type(scope): description`".into(),
+            Some(1),
+        ));
+        return (None, warnings);
+    };
+
+    if !config.types.is_empty() && !config.types.iter().any(|kind| kind == &parsed.kind) {
+        warnings.push(lint_warning(
+            "type-enum",
+            Error,
+            format!(
+                "type `{}` is not one of {}",
+                parsed.kind,
+                config.types.join(", ")
+            ),
+            Some(1),
+        ));
+    }
+    match parsed.scope.as_deref() {
+        None if config.require_scope => warnings.push(lint_warning(
+            "scope-empty",
+            Error,
+            "a scope is required".into(),
+            Some(1),
+        )),
+        Some(scope)
+            if !config.scopes.is_empty() && !config.scopes.iter().any(|known| known == scope) =>
+        {
+            warnings.push(lint_warning(
+                "scope-enum",
+                Error,
+                format!("scope `{scope}` is not one of {}", config.scopes.join(", ")),
+                Some(1),
+            ))
+        }
+        _ => {}
+    }
+    if parsed.description.is_empty() {
+        warnings.push(lint_warning(
+            "subject-empty",
+            Error,
+            "the description after the type is empty".into(),
+            Some(1),
+        ));
+    } else if parsed.description.ends_with('.') {
+        warnings.push(lint_warning(
+            "subject-full-stop",
+            Warning,
+            "the description should not end with a full stop".into(),
+            Some(1),
+        ));
+    }
+    if let Some(max) = config.max_subject_length {
+        let length = header.chars().count();
+        if length > max as usize {
+            warnings.push(lint_warning(
+                "subject-max-length",
+                Warning,
+                format!("the subject is {length} characters long; the limit is {max}"),
+                Some(1),
+            ));
+        }
+    }
+    if message.lines().nth(1).is_some_and(|line| !line.is_empty()) {
+        warnings.push(lint_warning(
+            "body-leading-blank",
+            Warning,
+            "leave a blank line between the subject and the body".into(),
+            Some(2),
+        ));
+    }
+
+    for (index, line) in message.lines().enumerate() {
+        let Some((token, value)) = line.split_once(':') else {
+            continue;
+        };
+        let normalized = token.to_ascii_uppercase().replace('-', " ");
+        if normalized != BREAKING_CHANGE {
+            continue;
+        }
+        let line = Some(index as u32 + 1);
+        if token != BREAKING_CHANGE && token != "BREAKING-CHANGE" {
+            warnings.push(lint_warning(
+                "footer-breaking-change",
+                Error,
+                format!("write `{BREAKING_CHANGE}` in upper case, not `{token}`"),
+                line,
+            ));
+        } else if value.trim().is_empty() {
+            warnings.push(lint_warning(
+                "footer-breaking-change",
+                Error,
+                "describe the breaking change after the footer token".into(),
+                line,
+            ));
+        }
+    }
+
+    (Some(parsed), warnings)
+}
+
+/// Parses `git count-objects -v`; sizes are reported in KiB.
+pub fn parse_count_objects(output: &str) -> GitObjectCounts {
+    let mut counts = GitObjectCounts::default();
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value: u64 = value.trim().parse().unwrap_or(0);
+        match key.trim() {
+            "count" => counts.loose_objects = value,
+            "size" => counts.loose_size = value * 1024,
+            "in-pack" => counts.packed_objects = value,
+            "packs" => counts.packs = value,
+            "size-pack" => counts.pack_size = value * 1024,
+            "prune-packable" => counts.prune_packable = value,
+            "garbage" => counts.garbage_files = value,
+            "size-garbage" => counts.garbage_size = value * 1024,
+            _ => {}
+        }
+    }
+    counts
+}
+
+/// Format passed to `git cat-file` for [`parse_largest_blobs`].
+pub const BLOB_SIZE_FORMAT: &str =
+    "--batch-check=%(objecttype) %(objectname) %(objectsize) %(objectsize:disk)";
+
+/// The `limit` largest blobs in `cat-file --batch-all-objects` output, largest
+/// first. Paths are filled in by [`assign_blob_paths`].
+pub fn parse_largest_blobs(output: &str, limit: usize) -> Vec<GitLargeBlob> {
+    let mut blobs: Vec<GitLargeBlob> = output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split(' ');
+            if parts.next()? != "blob" {
+                return None;
+            }
+            Some(GitLargeBlob {
+                oid: parts.next()?.to_string(),
+                size: parts.next()?.parse().ok()?,
+                disk_size: parts.next()?.parse().ok()?,
+                path: None,
+            })
+        })
+        .collect();
+    blobs.sort_by(|a, b| b.size.cmp(&a.size).then(a.oid.cmp(&b.oid)));
+    blobs.truncate(limit);
+    blobs
+}
+
+/// Fills in blob paths from `git rev-list --objects` output (`<oid> <path>`).
+pub fn assign_blob_paths(blobs: &mut [GitLargeBlob], rev_list: &str) {
+    let mut pending = blobs.len();
+    for line in rev_list.lines() {
+        if pending == 0 {
+            break;
+        }
+        let Some((oid, path)) = line.split_once(' ') else {
+            continue;
+        };
+        for blob in blobs.iter_mut() {
+            if blob.path.is_none() && blob.oid == oid {
+                blob.path = Some(path.to_string());
+                pending -= 1;
+            }
+        }
+    }
+}
+
+/// Splits one whitespace-separated token off the front of `line`.
+fn next_token(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim_start();
+    if line.is_empty() {
+        return None;
+    }
+    Some(line.split_once(char::is_whitespace).unwrap_or((line, "")))
+}
+
+/// One side of a range-diff header: `3:  a64698d`, or `-:  -------` when the
+/// commit has no counterpart.
+fn range_diff_side(index: &str, commit: &str) -> Option<(Option<u32>, Option<String>)> {
+    let index = index.strip_suffix(':')?;
+    if index == "-" {
+        return Some((None, None));
+    }
+    Some((Some(index.parse().ok()?), Some(commit.to_string())))
+}
+
+fn parse_range_diff_header(line: &str) -> Option<GitRangeDiffPair> {
+    let (old_index, rest) = next_token(line)?;
+    let (old_commit, rest) = next_token(rest)?;
+    let (marker, rest) = next_token(rest)?;
+    let (new_index, rest) = next_token(rest)?;
+    let (new_commit, rest) = next_token(rest)?;
+    let (old_index, old_commit) = range_diff_side(old_index, old_commit)?;
+    let (new_index, new_commit) = range_diff_side(new_index, new_commit)?;
+    Some(GitRangeDiffPair {
+        status: GitRangeDiffStatus::parse(marker)?,
+        old_index,
+        old_commit,
+        new_index,
+        new_commit,
+        subject: rest.trim_start().to_string(),
+        diff: None,
+    })
+}
+
+/// Parses `git range-diff --no-color`: a header per commit pair, followed by
+/// the diff of the two patches indented by four spaces.
+pub fn parse_range_diff(output: &str) -> Vec<GitRangeDiffPair> {
+    let mut pairs: Vec<GitRangeDiffPair> = Vec::new();
+    for line in output.lines() {
+        if let Some(diff_line) = line.strip_prefix("    ") {
+            if let Some(pair) = pairs.last_mut() {
+                let diff = pair.diff.get_or_insert_with(String::new);
+                if !diff.is_empty() {
+                    diff.push('\n');
+                }
+                diff.push_str(diff_line);
+            }
+            continue;
+        }
+        if let Some(pair) = parse_range_diff_header(line) {
+            pairs.push(pair);
+        }
+    }
+    pairs
+}
@@ -0,0 +1,441 @@
+//! Overview and batch operations across every repository in the workspace.
+
+use crate::{
+    db::Database,
+    git::{
+        models::{
+            GitError, GitErrorResponse, GitRepositorySummary, GitStreamRequest,
+            GitWorkspaceFetchHandle, GitWorkspaceFetchProgress, GitWorkspaceFetchRequest,
+            GitWorkspaceRequest,
+        },
+        operations::{fetch_args, repository_root, run_git_capture},
+        search,
+        service::GitService,
+        streaming::spawn_streaming_command,
+        util,
+    },
+};
+use log::error;
+use std::{
+    collections::{BTreeSet, HashMap},
+    future::Future,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::UNIX_EPOCH,
+};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::Semaphore;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+pub const WORKSPACE_FETCH_EVENT: &str = "git://workspace-fetch";
+
+const DEFAULT_CONCURRENCY: usize = 4;
+const MAX_CONCURRENCY: usize = 16;
+const DEFAULT_SCAN_DEPTH: usize = 3;
+const MAX_SCAN_DEPTH: usize = 8;
+
+/// Directories never searched for nested repositories.
+const SKIPPED_DIRECTORIES: &[&str] = &["node_modules", "target"];
+
+fn concurrency(request: &GitWorkspaceRequest) -> usize {
+    request
+        .concurrency
+        .unwrap_or(DEFAULT_CONCURRENCY)
+        .clamp(1, MAX_CONCURRENCY)
+}
+
+/// Runs `task` for every path with at most `limit` running at once, keeping
+/// the input order in the results. A task that panics is replaced by
+/// `failed(path)`, so there is one result per path.
+async fn run_bounded<T, F, Fut, E>(paths: Vec<String>, limit: usize, task: F, failed: E) -> Vec<T>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+    E: Fn(String) -> T,
+{
+    let semaphore = Arc::new(Semaphore::new(limit));
+    let handles: Vec<_> = paths
+        .into_iter()
+        .map(|path| {
+            let semaphore = semaphore.clone();
+            let future = task(path.clone());
+            let handle = tauri::async_runtime::spawn(async move {
+                let _permit = semaphore.acquire().await;
+                future.await
+            });
+            (path, handle)
+        })
+        .collect();
+
+    let mut results = Vec::with_capacity(handles.len());
+    for (path, handle) in handles {
+        results.push(match handle.await {
+            Ok(result) => result,
+            Err(err) => {
+                error!("git:workspace task for {path} failed: {err}");
+                failed(path)
+            }
+        });
+    }
+    results
+}
+
+/// Repositories under `root`, without descending into a repository once
+/// found or into hidden and dependency directories.
+fn scan_repositories(root: &Path, max_depth: usize) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut entries = WalkDir::new(root)
+        .max_depth(max_depth)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            entry.file_type().is_dir()
+                && (entry.depth() == 0
+                    || (!name.starts_with('.') && !SKIPPED_DIRECTORIES.contains(&name.as_ref())))
+        });
+    while let Some(entry) = entries.next() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        // `.git` is a file in linked worktrees and submodules.
+        if entry.path().join(".git").exists() {
+            found.push(entry.path().to_string_lossy().to_string());
+            entries.skip_current_dir();
+        }
+    }
+    found
+}
+
+/// Repository roots covered by `request`, sorted and without duplicates.
+async fn workspace_repositories(
+    app: &AppHandle,
+    database: &Database,
+    request: &GitWorkspaceRequest,
+) -> Result<Vec<String>, GitErrorResponse> {
+    if let Some(directory) = request.directory.as_deref() {
+        let root = util::canonicalize_path(directory).map_err(GitErrorResponse::from)?;
+        let depth = request
+            .max_depth
+            .unwrap_or(DEFAULT_SCAN_DEPTH)
+            .min(MAX_SCAN_DEPTH);
+        return tauri::async_runtime::spawn_blocking(move || scan_repositories(&root, depth))
+            .await
+            .map_err(|e| GitErrorResponse::from(GitError::Spawn(e.to_string())));
+    }
+
+    let projects: Vec<(String,)> = sqlx::query_as("SELECT path FROM projects")
+        .fetch_all(database.pool())
+        .await
+        .map_err(|err| GitErrorResponse::from(GitError::from(err)))?;
+    let projects = projects.into_iter().map(|(path,)| path).collect();
+
+    // Projects that are not inside a repository are left out; several
+    // projects in one repository collapse to its root.
+    let roots = run_bounded(
+        projects,
+        concurrency(request),
+        |path| {
+            let app = app.clone();
+            async move {
+                let service = app.state::<GitService>();
+                repository_root(&app, &service, &path).await.ok()
+            }
+        },
+        |_| None,
+    )
+    .await;
+    let roots: BTreeSet<String> = roots
+        .into_iter()
+        .flatten()
+        .map(|root| root.to_string_lossy().to_string())
+        .collect();
+    Ok(roots.into_iter().collect())
+}
+
+async fn git_file_path(
+    app: &AppHandle,
+    service: &GitService,
+    repository_path: &str,
+    name: &str,
+) -> Option<PathBuf> {
+    let outcome = run_git_capture(
+        app,
+        service,
+        repository_path,
+        vec!["rev-parse".into(), "--git-path".into(), name.into()],
+        None,
+    )
+    .await
+    .ok()
+    .filter(|outcome| outcome.success)?;
+    // Relative to the working directory unless git printed an absolute path.
+    let working_dir = util::canonicalize_path(repository_path).ok()?;
+    Some(working_dir.join(outcome.stdout))
+}
+
+/// Summary of `path` with nothing read yet.
+fn empty_summary(path: String) -> GitRepositorySummary {
+    let name = Path::new(&path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.clone());
+    GitRepositorySummary {
+        path,
+        name,
+        branch: None,
+        upstream: None,
+        detached: false,
+        ahead: 0,
+        behind: 0,
+        staged: 0,
+        unstaged: 0,
+        conflicts: 0,
+        untracked: 0,
+        stash_count: 0,
+        last_fetch_at: None,
+        error: None,
+    }
+}
+
+async fn summarize_repository(
+    app: &AppHandle,
+    service: &GitService,
+    path: String,
+) -> GitRepositorySummary {
+    let mut summary = empty_summary(path);
+
+    let status = match run_git_capture(
+        app,
+        service,
+        &summary.path,
+        vec![
+            "status".into(),
+            "--branch".into(),
+            "--porcelain=v1".into(),
+            "-z".into(),
+        ],
+        None,
+    )
+    .await
+    {
+        Ok(outcome) if outcome.success => util::parse_status(&outcome.stdout),
+        Ok(outcome) => {
+            summary.error =
+                Some(GitErrorResponse::from_outcome(&outcome, "failed to read status").message);
+            return summary;
+        }
+        Err(err) => {
+            summary.error = Some(err.to_string());
+            return summary;
+        }
+    };
+    summary.branch = status.branch;
+    summary.upstream = status.upstream;
+    summary.detached = status.detached;
+    summary.ahead = status.ahead;
+    summary.behind = status.behind;
+    summary.staged = status.staged.len() as u32;
+    summary.unstaged = status.unstaged.len() as u32;
+    summary.conflicts = status.conflicts.len() as u32;
+    summary.untracked = status.untracked.len() as u32;
+
+    // Fails when there is no stash.
+    let stashes = run_git_capture(
+        app,
+        service,
+        &summary.path,
+        vec![
+            "rev-list".into(),
+            "--walk-reflogs".into(),
+            "--count".into(),
+            "refs/stash".into(),
+        ],
+        None,
+    )
+    .await;
+    if let Some(outcome) = stashes.ok().filter(|outcome| outcome.success) {
+        summary.stash_count = outcome.stdout.parse().unwrap_or(0);
+    }
+
+    summary.last_fetch_at = git_file_path(app, service, &summary.path, "FETCH_HEAD")
+        .await
+        .and_then(|path| std::fs::metadata(path).ok())
+        .and_then(|metadata| metadata.modified().ok())
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_millis() as i64);
+
+    summary
+}
+
+/// Branch, working tree, upstream, stash and fetch state of every
+/// repository in the workspace, read a few repositories at a time.
+#[tauri::command]
+pub async fn git_workspace_overview(
+    app: AppHandle,
+    database: State<'_, Database>,
+    request: GitWorkspaceRequest,
+) -> Result<Vec<GitRepositorySummary>, GitErrorResponse> {
+    let repositories = workspace_repositories(&app, &database, &request).await?;
+    Ok(run_bounded(
+        repositories,
+        concurrency(&request),
+        |path| {
+            let app = app.clone();
+            async move {
+                let service = app.state::<GitService>();
+                summarize_repository(&app, &service, path).await
+            }
+        },
+        |path| GitRepositorySummary {
+            error: Some("reading the repository failed unexpectedly".into()),
+            ..empty_summary(path)
+        },
+    )
+    .await)
+}
+
+struct BatchProgress {
+    batch_id: String,
+    total: u32,
+    completed: AtomicU32,
+    failed: AtomicU32,
+}
+
+impl BatchProgress {
+    fn emit(
+        &self,
+        app: &AppHandle,
+        repository_path: &str,
+        command_id: Option<String>,
+        result: Option<Result<(), String>>,
+    ) {
+        let (completed, failed) = match result.as_ref() {
+            None => (
+                self.completed.load(Ordering::SeqCst),
+                self.failed.load(Ordering::SeqCst),
+            ),
+            Some(outcome) => {
+                if outcome.is_err() {
+                    self.failed.fetch_add(1, Ordering::SeqCst);
+                }
+                (
+                    self.completed.fetch_add(1, Ordering::SeqCst) + 1,
+                    self.failed.load(Ordering::SeqCst),
+                )
+            }
+        };
+        let payload = GitWorkspaceFetchProgress {
+            batch_id: self.batch_id.clone(),
+            repository_path: repository_path.to_string(),
+            command_id,
+            success: result.as_ref().map(Result::is_ok),
+            error: result.and_then(Result::err),
+            total: self.total,
+            completed,
+            failed,
+            done: completed == self.total,
+        };
+        let _ = app.emit(WORKSPACE_FETCH_EVENT, payload);
+    }
+}
+
+async fn fetch_repository(
+    app: AppHandle,
+    path: String,
+    args: Vec<String>,
+    auth: Option<crate::git::auth::GitAuth>,
+    progress: Arc<BatchProgress>,
+) {
+    let command_id = Uuid::new_v4().to_string();
+    progress.emit(&app, &path, Some(command_id.clone()), None);
+
+    let request = GitStreamRequest {
+        repository_path: path.clone(),
+        remote: None,
+        branch: None,
+        auth,
+        command_id: Some(command_id.clone()),
+    };
+    let service = app.state::<GitService>();
+    let result =
+        match spawn_streaming_command(app.clone(), service, request, args, HashMap::new()).await {
+            Ok((_, completion)) => match completion.await {
+                Ok(completion) if completion.success => Ok(()),
+                Ok(completion) => Err(match completion.exit_code {
+                    Some(code) => format!("fetch exited with code {code}"),
+                    None => "fetch was terminated".to_string(),
+                }),
+                Err(_) => Err("fetch ended without reporting a result".to_string()),
+            },
+            Err(err) => Err(err.message),
+        };
+
+    if result.is_ok() {
+        search::schedule_index_update(app.clone(), path.clone());
+    }
+    progress.emit(&app, &path, Some(command_id), Some(result));
+}
+
+/// Fetches all remotes of every repository in the workspace, a few at a
+/// time. Each fetch streams on `git://stream`; batch progress is reported
+/// on `git://workspace-fetch`.
+#[tauri::command]
+pub async fn git_workspace_fetch(
+    app: AppHandle,
+    database: State<'_, Database>,
+    request: GitWorkspaceFetchRequest,
+) -> Result<GitWorkspaceFetchHandle, GitErrorResponse> {
+    let args = fetch_args(None, None, request.prune.unwrap_or(false), false, &[])
+        .map_err(GitErrorResponse::from)?;
+    let repositories = workspace_repositories(&app, &database, &request.workspace).await?;
+    let batch_id = request
+        .batch_id
+        .clone()
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let progress = Arc::new(BatchProgress {
+        batch_id: batch_id.clone(),
+        total: repositories.len() as u32,
+        completed: AtomicU32::new(0),
+        failed: AtomicU32::new(0),
+    });
+    let limit = concurrency(&request.workspace);
+    let auth = request.auth;
+    let paths = repositories.clone();
+    let task_app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        // Nothing will finish, so report the empty batch as done right away.
+        if paths.is_empty() {
+            progress.emit(&task_app, "", None, None);
+            return;
+        }
+        run_bounded(
+            paths,
+            limit,
+            |path| {
+                fetch_repository(
+                    task_app.clone(),
+                    path,
+                    args.clone(),
+                    auth.clone(),
+                    progress.clone(),
+                )
+            },
+            |path| {
+                let error = "fetch failed unexpectedly".to_string();
+                progress.emit(&task_app, &path, None, Some(Err(error)));
+            },
+        )
+        .await;
+    });
+
+    Ok(GitWorkspaceFetchHandle {
+        batch_id,
+        repositories,
+    })
+}
@@ -0,0 +1,362 @@
+//! Argument policy for git calls the front-end controls directly: which
+//! subcommands and options `git_run` may pass through, which config keys
+//! and values the config editor may write, and how fetches are built.
+
+use std::path::{Component, Path};
+
+/// Subcommands `git_run` may start. Everything else, notably `config`,
+/// `submodule` and transport plumbing, has to go through a typed command.
+const ALLOWED_SUBCOMMANDS: &[&str] = &[
+    "add",
+    "blame",
+    "branch",
+    "cat-file",
+    "checkout",
+    "cherry-pick",
+    "commit",
+    "describe",
+    "diff",
+    "fetch",
+    "for-each-ref",
+    "grep",
+    "log",
+    "ls-files",
+    "ls-remote",
+    "ls-tree",
+    "merge",
+    "merge-base",
+    "mv",
+    "name-rev",
+    "pull",
+    "push",
+    "rebase",
+    "reflog",
+    "remote",
+    "reset",
+    "restore",
+    "rev-list",
+    "rev-parse",
+    "revert",
+    "rm",
+    "shortlog",
+    "show",
+    "show-ref",
+    "stash",
+    "status",
+    "switch",
+    "symbolic-ref",
+    "tag",
+];
+
+/// Global options allowed before the subcommand.
+const ALLOWED_GLOBAL_OPTIONS: &[&str] = &[
+    "--no-pager",
+    "-P",
+    "--literal-pathspecs",
+    "--no-optional-locks",
+    "--no-replace-objects",
+];
+
+/// Keys that may be overridden with `-c key=value`; others (for example
+/// `core.sshCommand` or `core.pager`) can run arbitrary programs.
+const SAFE_CONFIG_KEYS: &[&str] = &[
+    "color.ui",
+    "core.quotepath",
+    "log.showsignature",
+    "diff.renames",
+];
+
+/// Config keys the config editor may not write because git runs or loads
+/// their value: a program, a hook directory, another config file or a
+/// worktree outside the project.
+const DENIED_CONFIG_KEYS: &[&str] = &[
+    "core.alternaterefscommand",
+    "core.askpass",
+    "core.editor",
+    "core.fsmonitor",
+    "core.gitproxy",
+    "core.hookspath",
+    "core.pager",
+    "core.sshcommand",
+    "core.worktree",
+    "credential.helper",
+    "diff.external",
+    "gpg.program",
+    "include.path",
+    "interactive.difffilter",
+    "protocol.allow",
+    "sendemail.sendmailcmd",
+    "sendemail.smtpserver",
+    "sequence.editor",
+    "uploadpack.packobjectshook",
+    "web.browser",
+];
+
+/// Sections where every key names a program (`alias.*` values starting with
+/// `!` run in a shell) or includes another config file.
+const DENIED_CONFIG_SECTIONS: &[&str] = &["alias", "filter", "includeif", "pager"];
+
+/// Variable names that run programs inside a subsection, such as
+/// `diff.<driver>.command` or `remote.<name>.uploadpack`.
+const DENIED_SUBSECTION_NAMES: &[(&str, &str)] = &[
+    ("browser", "cmd"),
+    ("credential", "helper"),
+    ("diff", "command"),
+    ("diff", "textconv"),
+    ("difftool", "cmd"),
+    ("difftool", "path"),
+    ("gpg", "defaultkeycommand"),
+    ("gpg", "program"),
+    ("man", "cmd"),
+    ("merge", "driver"),
+    ("mergetool", "cmd"),
+    ("mergetool", "path"),
+    ("protocol", "allow"),
+    ("remote", "receivepack"),
+    ("remote", "uploadpack"),
+    ("remote", "vcs"),
+    ("submodule", "update"),
+];
+
+/// Options that run programs, write files chosen by the caller or read files
+/// from outside the repository. Git accepts unambiguous abbreviations of long
+/// options, so any prefix of these is rejected too.
+const DENIED_OPTIONS: &[&str] = &[
+    "--upload-pack",
+    "--receive-pack",
+    "--exec",
+    "--output",
+    "--open-files-in-pager",
+    "--template",
+    "--config",
+    "--file",
+    "--no-index",
+    "--contents",
+    "--pathspec-from-file",
+    "--ignore-revs-file",
+    "--exclude-from",
+    "--excludes-file",
+];
+
+/// Complete options that are also prefixes of a denied one. Git takes an
+/// exact match over an abbreviation, so these stay allowed.
+const EXACT_OPTIONS: &[&str] = &["--exclude", "--ignore-rev"];
+
+/// Short options denied per subcommand, as the long forms above:
+/// `rebase -x <cmd>` runs a shell command after each commit, `grep -O` opens
+/// matches in a pager of the caller's choosing, `ls-remote -u` is
+/// `--upload-pack`, `grep -f` reads patterns from a file and `-t`/`-F` read
+/// a template or message file.
+const DENIED_SHORT_OPTIONS: &[(&str, &[char])] = &[
+    ("commit", &['t', 'F']),
+    ("grep", &['O', 'f']),
+    ("ls-remote", &['u']),
+    ("merge", &['F']),
+    ("rebase", &['x']),
+    ("tag", &['F']),
+];
+
+/// Short options that take a value, per subcommand. In a bundle such as
+/// `-iX<option>` the rest of the argument after one of these is its value,
+/// not more options.
+const VALUE_SHORT_OPTIONS: &[(&str, &[char])] = &[
+    ("commit", &['m', 'c', 'C', 'S', 'u']),
+    ("grep", &['e', 'A', 'B', 'C', 'm']),
+    ("ls-remote", &['o']),
+    ("merge", &['m', 's', 'X', 'S']),
+    ("rebase", &['s', 'X', 'C', 'S']),
+    ("tag", &['m', 'u']),
+];
+
+fn short_options<'a>(table: &[(&str, &'a [char])], subcommand: &str) -> &'a [char] {
+    table
+        .iter()
+        .find(|(name, _)| *name == subcommand)
+        .map(|(_, options)| *options)
+        .unwrap_or(&[])
+}
+
+fn denied_option(subcommand: &str, arg: &str) -> bool {
+    let name = arg.split_once('=').map(|(name, _)| name).unwrap_or(arg);
+    if name.len() > 2
+        && name.starts_with("--")
+        && !EXACT_OPTIONS.contains(&name)
+        && DENIED_OPTIONS.iter().any(|option| option.starts_with(name))
+    {
+        return true;
+    }
+    let Some(bundle) = arg.strip_prefix('-').filter(|rest| !rest.starts_with('-')) else {
+        return false;
+    };
+    let denied = short_options(DENIED_SHORT_OPTIONS, subcommand);
+    let takes_value = short_options(VALUE_SHORT_OPTIONS, subcommand);
+    for option in bundle.chars() {
+        if denied.contains(&option) {
+            return true;
+        }
+        if takes_value.contains(&option) {
+            break;
+        }
+    }
+    false
+}
+
+fn outside_path(arg: &str) -> bool {
+    let path = Path::new(arg);
+    path.has_root()
+        || path.is_absolute()
+        || path
+            .components()
+            .any(|component| matches!(component, Component::ParentDir | Component::Prefix(_)))
+}
+
+/// Checks `args` against the policy, returning the reason when they are
+/// rejected.
+pub fn check_run_args(args: &[String]) -> Result<(), String> {
+    if args.iter().any(|arg| arg.contains('\0')) {
+        return Err("arguments may not contain null bytes".into());
+    }
+
+    let mut iter = args.iter().map(String::as_str);
+    let mut subcommand = None;
+    while let Some(arg) = iter.next() {
+        if arg == "-c" {
+            let Some(setting) = iter.next() else {
+                return Err("`-c` needs a key=value pair".into());
+            };
+            let key = setting
+                .split_once('=')
+                .map(|(key, _)| key)
+                .unwrap_or(setting)
+                .to_ascii_lowercase();
+            if !SAFE_CONFIG_KEYS.contains(&key.as_str()) {
+                return Err(format!("overriding `{key}` with -c is not allowed"));
+            }
+            continue;
+        }
+        if arg.starts_with('-') {
+            if !ALLOWED_GLOBAL_OPTIONS.contains(&arg) {
+                return Err(format!("global option `{arg}` is not allowed"));
+            }
+            continue;
+        }
+        subcommand = Some(arg);
+        break;
+    }
+
+    let Some(subcommand) = subcommand else {
+        return Err("no git subcommand given".into());
+    };
+    if !ALLOWED_SUBCOMMANDS.contains(&subcommand) {
+        return Err(format!("`git {subcommand}` is not allowed through git_run"));
+    }
+
+    let mut options = true;
+    for arg in iter {
+        // Everything after `--` is a path.
+        if options && arg == "--" {
+            options = false;
+            continue;
+        }
+        if options && denied_option(subcommand, arg) {
+            return Err(format!(
+                "option `{arg}` is not allowed for git {subcommand}"
+            ));
+        }
+        // `git diff` falls back to `--no-index` when a path lies outside the
+        // worktree, which would let it read any file.
+        if subcommand == "diff" && (!options || !arg.starts_with('-')) && outside_path(arg) {
+            return Err(format!("path `{arg}` is outside the repository"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that the config editor may write or unset `key`, returning the
+/// reason when it may not. `key` is compared case-insensitively except for
+/// its subsection, as git does.
+pub fn check_config_key(key: &str) -> Result<(), String> {
+    let (section, rest) = key.split_once('.').unwrap_or((key, ""));
+    let section = section.to_ascii_lowercase();
+    let name = rest.rsplit('.').next().unwrap_or(rest).to_ascii_lowercase();
+    let has_subsection = rest.contains('.');
+    let denied = DENIED_CONFIG_SECTIONS.contains(&section.as_str())
+        || if has_subsection {
+            DENIED_SUBSECTION_NAMES.contains(&(section.as_str(), name.as_str()))
+        } else {
+            DENIED_CONFIG_KEYS.contains(&format!("{section}.{name}").as_str())
+        };
+    if denied {
+        Err(format!(
+            "`{key}` runs or loads programs and cannot be edited here"
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks a value for the config editor: git would parse a leading `-` as an
+/// option and a null byte cannot be passed as an argument.
+pub fn check_config_value(value: &str) -> Result<(), String> {
+    if value.contains('\0') {
+        Err("value may not contain null bytes".into())
+    } else if value.starts_with('-') {
+        Err("value may not start with `-`".into())
+    } else {
+        Ok(())
+    }
+}
+
+/// A value git must read as a name rather than an option: non-empty, without
+/// null bytes and not starting with `-`.
+fn positional(value: &str, field: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        Err(format!("{field} cannot be empty"))
+    } else if value.contains('\0') {
+        Err(format!("{field} may not contain null bytes"))
+    } else if value.starts_with('-') {
+        Err(format!("{field} may not start with `-`, got `{value}`"))
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+/// Arguments for `git fetch`: every remote when `remote` is unset, otherwise
+/// that remote with an optional branch and refspecs.
+pub fn fetch_args(
+    remote: Option<&str>,
+    branch: Option<&str>,
+    prune: bool,
+    tags: bool,
+    refspecs: &[String],
+) -> Result<Vec<String>, String> {
+    let mut args = vec!["fetch".into()];
+    if prune {
+        args.push("--prune".into());
+    }
+    if tags {
+        args.push("--tags".into());
+    }
+
+    match remote {
+        Some(remote) => {
+            args.push(positional(remote, "remote")?);
+            if let Some(branch) = branch {
+                args.push(positional(branch, "branch")?);
+            }
+            for refspec in refspecs {
+                args.push(positional(refspec, "refspec")?);
+            }
+        }
+        None => {
+            if branch.is_some() || !refspecs.is_empty() {
+                return Err("a remote is required when fetching specific refspecs".into());
+            }
+            args.push("--all".into());
+        }
+    }
+
+    Ok(args)
+}
@@ -1,12 +1,18 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+pub mod bisect_runner;
+pub mod credential_helper;
+#[cfg(feature = "desktop")]
+mod db;
 #[cfg(feature = "desktop")]
 mod error;
 #[cfg(feature = "desktop")]
 mod fs;
 #[cfg(feature = "desktop")]
 mod git;
+pub mod git_args;
 pub mod migrations;
+pub mod porcelain;
 #[cfg(feature = "desktop")]
 mod runs;
 pub mod terminal;
@@ -100,6 +106,12 @@ pub fn run() {
             });
 
             app.manage(git::service::GitService::new());
+            let credential_store =
+                git::credentials::CredentialStore::new(app.path().app_data_dir()?);
+            app.manage(git::credential_server::CredentialServer::start(
+                credential_store.clone(),
+            ));
+            app.manage(credential_store);
             app.manage(fs::FsWatcherManager::new());
 
             let migrations: Vec<tauri_plugin_sql::Migration> = migrations::definitions()
@@ -119,6 +131,17 @@ pub fn run() {
                 })?;
             info!("db:migrate:ok");
 
+            let database = tauri::async_runtime::block_on(async {
+                let database = db::Database::connect_lazy(&db_url)?;
+                database.migrate().await?;
+                Ok::<_, AppError>(database)
+            })
+            .map_err(|err| {
+                error!("db:connect:err {err}");
+                Box::<dyn std::error::Error>::from(err)
+            })?;
+            app.manage(database);
+
             info!("app:booted");
 
             Ok(())
@@ -148,9 +171,79 @@ pub fn run() {
             git::operations::git_stash_apply,
             git::operations::git_remote_list,
             git::operations::git_fetch_all,
+            git::operations::git_clone,
             git::operations::git_pull,
             git::operations::git_push,
+            git::operations::git_sync,
             git::operations::git_run,
+            git::bisect::git_bisect_start,
+            git::bisect::git_bisect_mark,
+            git::bisect::git_bisect_status,
+            git::bisect::git_bisect_reset,
+            git::bisect::git_bisect_run,
+            git::journal::git_reflog,
+            git::journal::git_operations_list,
+            git::journal::git_undo_last_operation,
+            git::reset::git_reset_preview,
+            git::reset::git_reset,
+            git::state::git_operation_state,
+            git::patch::git_format_patch,
+            git::patch::git_patch_preview,
+            git::patch::git_am,
+            git::patch::git_am_action,
+            git::search::git_search,
+            git::stats::git_repository_stats,
+            git::sparse::git_sparse_checkout_list,
+            git::sparse::git_sparse_checkout_init,
+            git::sparse::git_sparse_checkout_set,
+            git::sparse::git_sparse_checkout_add,
+            git::sparse::git_sparse_checkout_disable,
+            git::lfs::git_lfs_status,
+            git::lfs::git_lfs_pointer,
+            git::lfs::git_lfs_track,
+            git::lfs::git_lfs_untrack,
+            git::lfs::git_lfs_pull,
+            git::lfs::git_lfs_locks,
+            git::lfs::git_lfs_lock,
+            git::lfs::git_lfs_unlock,
+            git::hooks::git_hooks_list,
+            git::hooks::git_hook_read,
+            git::hooks::git_hook_set_enabled,
+            git::hooks::git_hooks_install_template,
+            git::hooks::git_hooks_preview,
+            git::workspace::git_workspace_overview,
+            git::workspace::git_workspace_fetch,
+            git::commit_message::git_commit_template,
+            git::commit_message::git_commit_lint_config,
+            git::commit_message::git_commit_lint_config_set,
+            git::commit_message::git_commit_lint,
+            git::commit_message::git_commit_suggestions,
+            git::archive::git_archive,
+            git::maintenance::git_size_report,
+            git::maintenance::git_gc,
+            git::maintenance::git_maintenance_run,
+            git::maintenance::git_fsck,
+            git::maintenance::git_prune,
+            git::maintenance::git_index_lock_status,
+            git::maintenance::git_index_lock_remove,
+            git::compare::git_compare,
+            git::compare::git_compare_file,
+            git::compare::git_range_diff,
+            git::ignore::git_check_ignore,
+            git::ignore::git_ignore_add,
+            git::attributes::git_check_attr,
+            git::config::git_config_list,
+            git::config::git_config_set,
+            git::config::git_config_unset,
+            git::config::git_config_common,
+            git::config::git_config_common_set,
+            git::policy::git_run_audit,
+            git::credentials::git_credentials_list,
+            git::credentials::git_credentials_add,
+            git::credentials::git_credentials_remove,
+            git::ssh::git_ssh_trust_host,
+            git::ssh::git_ssh_agent_info,
+            git::ssh::git_ssh_list_keys,
             fs::register_project_root,
             fs::unregister_project_root,
             runs::detect_project_runs,
@@ -1,3 +1,10 @@
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(code) = desktop_lib::credential_helper::run_from_args(&args)
+        .or_else(|| desktop_lib::bisect_runner::run_from_args(&args))
+    {
+        std::process::exit(code);
+    }
+
     desktop_lib::run();
 }
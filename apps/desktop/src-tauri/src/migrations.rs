@@ -101,6 +101,109 @@ pub fn definitions() -> Vec<MigrationDefinition> {
         "#,
             kind: MigrationKind::Up,
         },
+        MigrationDefinition {
+            version: 4,
+            description: "add git operation journal",
+            sql: r#"
+        CREATE TABLE IF NOT EXISTS git_operations (
+            id TEXT PRIMARY KEY,
+            repository_path TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            ref_name TEXT,
+            previous_head TEXT,
+            previous_oid TEXT,
+            new_oid TEXT,
+            description TEXT NOT NULL,
+            reset_mode TEXT,
+            created_at INTEGER NOT NULL,
+            undone_at INTEGER
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_git_operations_repository
+            ON git_operations (repository_path, created_at);
+        "#,
+            kind: MigrationKind::Up,
+        },
+        MigrationDefinition {
+            version: 5,
+            description: "add git commit search index",
+            sql: r#"
+        CREATE VIRTUAL TABLE IF NOT EXISTS git_commit_index USING fts5(
+            repository_path UNINDEXED,
+            hash UNINDEXED,
+            author,
+            email,
+            subject,
+            body,
+            committed_at UNINDEXED,
+            tokenize = 'unicode61'
+        );
+
+        CREATE TABLE IF NOT EXISTS git_commit_index_state (
+            repository_path TEXT PRIMARY KEY,
+            indexed_tips TEXT NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
+        "#,
+            kind: MigrationKind::Up,
+        },
+        MigrationDefinition {
+            version: 6,
+            description: "add git repository statistics cache",
+            sql: r#"
+        CREATE TABLE IF NOT EXISTS git_repository_stats (
+            repository_path TEXT PRIMARY KEY,
+            head_oid TEXT NOT NULL,
+            stats TEXT NOT NULL,
+            computed_at INTEGER NOT NULL
+        );
+        "#,
+            kind: MigrationKind::Up,
+        },
+        MigrationDefinition {
+            version: 7,
+            description: "mirror git config settings",
+            sql: r#"
+        ALTER TABLE settings ADD COLUMN git_user_name TEXT;
+        ALTER TABLE settings ADD COLUMN git_user_email TEXT;
+        ALTER TABLE settings ADD COLUMN git_default_branch TEXT;
+        ALTER TABLE settings ADD COLUMN git_pull_rebase TEXT;
+        ALTER TABLE settings ADD COLUMN git_signing_key TEXT;
+        "#,
+            kind: MigrationKind::Up,
+        },
+        MigrationDefinition {
+            version: 8,
+            description: "add git_run audit log",
+            sql: r#"
+        CREATE TABLE IF NOT EXISTS git_run_audit (
+            id TEXT PRIMARY KEY,
+            cwd TEXT NOT NULL,
+            args TEXT NOT NULL,
+            status TEXT NOT NULL,
+            exit_code INTEGER,
+            message TEXT,
+            duration_ms INTEGER,
+            created_at INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_git_run_audit_created_at
+            ON git_run_audit(created_at);
+        "#,
+            kind: MigrationKind::Up,
+        },
+        MigrationDefinition {
+            version: 9,
+            description: "add commit message lint configuration",
+            sql: r#"
+        CREATE TABLE IF NOT EXISTS git_commit_lint_config (
+            repository_path TEXT PRIMARY KEY,
+            config TEXT NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
+        "#,
+            kind: MigrationKind::Up,
+        },
     ]
 }
 
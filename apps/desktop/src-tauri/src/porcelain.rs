@@ -0,0 +1,41 @@
+//! Reading `git status --porcelain=v1 -z --branch` output.
+//!
+//! Captured git output has surrounding whitespace trimmed, which would eat the
+//! leading space of an unstaged first entry (` M path`). Asking for the
+//! `## branch` header keeps every entry away from the start of the output.
+
+/// Arguments that produce the output [`changed_paths`] reads.
+pub const STATUS_ARGS: &[&str] = &["status", "--porcelain=v1", "-z", "--branch"];
+
+/// A tracked path with its two-letter `XY` status code.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StatusEntry {
+    pub code: String,
+    pub path: String,
+}
+
+/// Entries of porcelain status output, skipping the branch header and the
+/// original path that follows renames and copies.
+pub fn changed_paths(output: &str) -> Vec<StatusEntry> {
+    let mut changed = Vec::new();
+    let mut entries = output.split('\0');
+    while let Some(entry) = entries.next() {
+        if entry.starts_with("## ") {
+            continue;
+        }
+        let (Some(code), Some(path)) = (entry.get(..2), entry.get(3..)) else {
+            continue;
+        };
+        if path.is_empty() {
+            continue;
+        }
+        changed.push(StatusEntry {
+            code: code.to_string(),
+            path: path.to_string(),
+        });
+        if code.starts_with(['R', 'C']) {
+            entries.next();
+        }
+    }
+    changed
+}
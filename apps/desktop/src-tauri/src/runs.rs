@@ -42,118 +42,591 @@ pub fn detect_project_runs(path: String) -> Result<Vec<RunSuggestion>, String> {
     Ok(suggestions)
 }
 
-fn detect_node(root: &Path, suggestions: &mut Vec<RunSuggestion>) {
-    let package_json = root.join("package.json");
-    if !package_json.exists() {
-        return;
+/// Scripts suggested first, in this order; the rest follow alphabetically.
+const PRIMARY_NODE_SCRIPTS: [&str; 4] = ["dev", "start", "build", "test"];
+
+/// Lifecycle scripts npm runs on its own during install and publish.
+const NODE_LIFECYCLE_SCRIPTS: [&str; 8] = [
+    "preinstall",
+    "install",
+    "postinstall",
+    "prepare",
+    "prepublish",
+    "prepublishOnly",
+    "prepack",
+    "postpack",
+];
+
+/// How deep `**` in a workspace pattern searches for packages.
+const WORKSPACE_GLOB_DEPTH: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodePackageManager {
+    Npm,
+    YarnClassic,
+    YarnBerry,
+    Pnpm,
+    Bun,
+}
+
+impl NodePackageManager {
+    fn command(&self) -> &'static str {
+        match self {
+            Self::Npm => "npm",
+            Self::YarnClassic | Self::YarnBerry => "yarn",
+            Self::Pnpm => "pnpm",
+            Self::Bun => "bun",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Npm => "npm",
+            Self::YarnClassic => "Yarn classic",
+            Self::YarnBerry => "Yarn Berry",
+            Self::Pnpm => "pnpm",
+            Self::Bun => "Bun",
+        }
+    }
+
+    fn run_args(&self, script: &str) -> Vec<String> {
+        vec!["run".into(), script.to_string()]
+    }
+
+    /// Runs `script` of the workspace package `package` from the workspace
+    /// root.
+    fn workspace_args(&self, package: &str, script: &str) -> Vec<String> {
+        let args: [&str; 4] = match self {
+            Self::Npm => ["run", script, "--workspace", package],
+            Self::YarnClassic | Self::YarnBerry => ["workspace", package, "run", script],
+            Self::Pnpm => ["--filter", package, "run", script],
+            Self::Bun => ["run", "--filter", package, script],
+        };
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    /// Reads the `packageManager` field, e.g. `pnpm@9.1.0` or `yarn@4.2.2`.
+    fn from_spec(spec: &str) -> Option<Self> {
+        let (name, version) = spec.split_once('@').unwrap_or((spec, ""));
+        match name {
+            "npm" => Some(Self::Npm),
+            "pnpm" => Some(Self::Pnpm),
+            "bun" => Some(Self::Bun),
+            "yarn" => {
+                let major = version
+                    .split('.')
+                    .next()
+                    .and_then(|major| major.parse::<u32>().ok())
+                    .unwrap_or(1);
+                Some(if major >= 2 {
+                    Self::YarnBerry
+                } else {
+                    Self::YarnClassic
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn detect(root: &Path, package: &serde_json::Value) -> Self {
+        if let Some(manager) = package
+            .get("packageManager")
+            .and_then(|spec| spec.as_str())
+            .and_then(Self::from_spec)
+        {
+            return manager;
+        }
+
+        if root.join("bun.lockb").exists() || root.join("bun.lock").exists() {
+            return Self::Bun;
+        }
+        if root.join("pnpm-lock.yaml").exists() {
+            return Self::Pnpm;
+        }
+        if let Ok(lock) = fs::read_to_string(root.join("yarn.lock")) {
+            // Berry lockfiles are YAML with a `__metadata` entry.
+            return if lock.contains("__metadata:") || root.join(".yarnrc.yml").exists() {
+                Self::YarnBerry
+            } else {
+                Self::YarnClassic
+            };
+        }
+        Self::Npm
+    }
+}
+
+fn read_package_json(dir: &Path) -> Option<serde_json::Value> {
+    let contents = fs::read_to_string(dir.join("package.json")).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Script names worth running by hand, the primary ones first. `pre`/`post`
+/// hooks of other scripts and install lifecycle scripts are left out.
+fn node_scripts(package: &serde_json::Value) -> Vec<String> {
+    let Some(scripts) = package.get("scripts").and_then(|s| s.as_object()) else {
+        return Vec::new();
+    };
+
+    let is_hook = |name: &str| {
+        NODE_LIFECYCLE_SCRIPTS.contains(&name)
+            || ["pre", "post"].iter().any(|prefix| {
+                name.strip_prefix(prefix)
+                    .is_some_and(|target| scripts.contains_key(target))
+            })
+    };
+
+    let mut names: Vec<String> = PRIMARY_NODE_SCRIPTS
+        .iter()
+        .filter(|name| scripts.contains_key(**name))
+        .map(|name| name.to_string())
+        .collect();
+    let mut rest: Vec<String> = scripts
+        .keys()
+        .filter(|name| !PRIMARY_NODE_SCRIPTS.contains(&name.as_str()) && !is_hook(name))
+        .cloned()
+        .collect();
+    rest.sort();
+    names.extend(rest);
+    names
+}
+
+/// Workspace patterns from `package.json` (`workspaces` as a list or as
+/// `{ packages: [...] }`) or `pnpm-workspace.yaml`.
+fn workspace_patterns(root: &Path, package: &serde_json::Value) -> Vec<String> {
+    let workspaces = package.get("workspaces");
+    let list = workspaces.and_then(|w| w.as_array()).or_else(|| {
+        workspaces
+            .and_then(|w| w.get("packages"))
+            .and_then(|p| p.as_array())
+    });
+    if let Some(list) = list {
+        return list
+            .iter()
+            .filter_map(|pattern| pattern.as_str())
+            .map(str::to_string)
+            .collect();
+    }
+
+    // `pnpm-workspace.yaml` only needs its `packages:` list read.
+    let Ok(contents) = fs::read_to_string(root.join("pnpm-workspace.yaml")) else {
+        return Vec::new();
+    };
+    let mut patterns = Vec::new();
+    let mut in_packages = false;
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if !line.starts_with([' ', '\t', '-']) && !trimmed.is_empty() {
+            in_packages = trimmed == "packages:";
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix('-').filter(|_| in_packages) {
+            let item = item.split(" #").next().unwrap_or(item).trim();
+            patterns.push(item.trim_matches(['\'', '"']).to_string());
+        }
+    }
+    patterns
+}
+
+/// Matches a single path segment against a pattern where `*` stands for any
+/// run of characters.
+fn segment_matches(pattern: &str, name: &str) -> bool {
+    let Some((prefix, rest)) = pattern.split_once('*') else {
+        return pattern == name;
+    };
+    let Some(name) = name.strip_prefix(prefix) else {
+        return false;
+    };
+    if rest.is_empty() {
+        return true;
     }
+    (0..=name.len())
+        .filter(|idx| name.is_char_boundary(*idx))
+        .any(|idx| segment_matches(rest, &name[idx..]))
+}
 
-    let Ok(contents) = fs::read_to_string(&package_json) else {
+/// Directories under `dir` matching the remaining pattern segments that
+/// contain a `manifest` file.
+fn expand_workspace_segments(
+    dir: &Path,
+    segments: &[&str],
+    manifest: &str,
+    found: &mut Vec<PathBuf>,
+) {
+    let Some((segment, rest)) = segments.split_first() else {
+        if dir.join(manifest).is_file() {
+            found.push(dir.to_path_buf());
+        }
         return;
     };
 
-    let Ok(value) = serde_json::from_str::<serde_json::Value>(&contents) else {
+    if *segment == "**" {
+        for entry in WalkDir::new(dir)
+            .max_depth(WORKSPACE_GLOB_DEPTH)
+            .into_iter()
+            .filter_entry(|entry| entry.file_name() != "node_modules")
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_dir())
+        {
+            expand_workspace_segments(entry.path(), rest, manifest, found);
+        }
+        return;
+    }
+
+    if !segment.contains('*') {
+        expand_workspace_segments(&dir.join(segment), rest, manifest, found);
+        return;
+    }
+
+    let Ok(entries) = fs::read_dir(dir) else {
         return;
     };
+    for entry in entries.filter_map(Result::ok) {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name == "node_modules" || !entry.path().is_dir() {
+            continue;
+        }
+        if segment_matches(segment, &name) {
+            expand_workspace_segments(&entry.path(), rest, manifest, found);
+        }
+    }
+}
+
+/// Package directories matched by workspace `patterns`; patterns starting
+/// with `!` exclude directories.
+fn workspace_packages(root: &Path, patterns: &[String], manifest: &str) -> Vec<PathBuf> {
+    let mut included = Vec::new();
+    let mut excluded = Vec::new();
+    for pattern in patterns {
+        let (negated, pattern) = match pattern.strip_prefix('!') {
+            Some(pattern) => (true, pattern),
+            None => (false, pattern.as_str()),
+        };
+        let segments: Vec<&str> = pattern
+            .trim_start_matches("./")
+            .split('/')
+            .filter(|segment| !segment.is_empty() && *segment != ".")
+            .collect();
+        if segments.contains(&"..") {
+            continue;
+        }
+        let target = if negated {
+            &mut excluded
+        } else {
+            &mut included
+        };
+        expand_workspace_segments(root, &segments, manifest, target);
+    }
+
+    included.sort();
+    included.dedup();
+    included.retain(|dir| dir != root && !excluded.contains(dir));
+    included
+}
 
-    let Some(scripts) = value.get("scripts").and_then(|s| s.as_object()) else {
+fn detect_node(root: &Path, suggestions: &mut Vec<RunSuggestion>) {
+    let Some(package) = read_package_json(root) else {
         return;
     };
+    let manager = NodePackageManager::detect(root, &package);
 
-    for candidate in ["dev", "start"] {
-        if scripts.contains_key(candidate) {
+    for script in node_scripts(&package) {
+        let suggestion = RunSuggestion {
+            language: "node".into(),
+            command: manager.command().into(),
+            args: manager.run_args(&script),
+            env: HashMap::new(),
+            cwd: Some(path_to_string(root)),
+            reason: format!(
+                "Detected package.json with `{}` script ({})",
+                script,
+                manager.label()
+            ),
+        };
+        push_unique(suggestions, suggestion);
+    }
+
+    let patterns = workspace_patterns(root, &package);
+    for dir in workspace_packages(root, &patterns, "package.json") {
+        let Some(member) = read_package_json(&dir) else {
+            continue;
+        };
+        // Workspace packages are addressed by name from the root.
+        let Some(name) = member.get("name").and_then(|name| name.as_str()) else {
+            continue;
+        };
+        for script in node_scripts(&member) {
             let suggestion = RunSuggestion {
                 language: "node".into(),
-                command: "pnpm".into(),
-                args: vec!["run".into(), candidate.to_string()],
+                command: manager.command().into(),
+                args: manager.workspace_args(name, &script),
                 env: HashMap::new(),
                 cwd: Some(path_to_string(root)),
-                reason: format!("Detected package.json with `{}` script", candidate),
+                reason: format!(
+                    "Detected `{}` script in workspace package `{}` ({})",
+                    script,
+                    name,
+                    manager.label()
+                ),
             };
             push_unique(suggestions, suggestion);
-            break;
         }
     }
 }
 
-fn detect_python(root: &Path, suggestions: &mut Vec<RunSuggestion>) {
-    let has_pyproject = root.join("pyproject.toml").exists();
-    let has_requirements = root.join("requirements.txt").exists();
+/// Files whose presence marks a Python project.
+const PYTHON_PROJECT_FILES: [&str; 6] = [
+    "pyproject.toml",
+    "requirements.txt",
+    "setup.py",
+    "setup.cfg",
+    "Pipfile",
+    "manage.py",
+];
+
+/// Top-level modules checked for a Flask or FastAPI application.
+const PYTHON_APP_MODULES: [&str; 5] = ["app", "main", "server", "wsgi", "asgi"];
+
+/// How a Python project's commands are run: through its tool's runner, a
+/// local virtualenv, or the `python` on `PATH`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PythonRunner {
+    Uv,
+    Poetry,
+    Pdm,
+    Hatch,
+    Pipenv,
+    Venv(PathBuf),
+    System,
+}
 
-    if !has_pyproject && !has_requirements {
-        return;
-    }
+/// What to run: the interpreter with arguments, or an installed console
+/// script.
+enum PythonProgram {
+    Python(Vec<String>),
+    Script(String),
+}
 
-    let module_name = if has_pyproject {
-        parse_python_module(root).or_else(|| default_module_name(root))
-    } else {
-        default_module_name(root)
-    };
+impl PythonRunner {
+    fn detect(root: &Path, pyproject: Option<&toml::Value>) -> Self {
+        let has_tool = |name: &str| {
+            pyproject
+                .and_then(|value| value.get("tool"))
+                .and_then(|tool| tool.get(name))
+                .is_some()
+        };
 
-    let Some(module) = module_name else {
-        return;
-    };
+        if root.join("uv.lock").exists() || root.join("uv.toml").exists() || has_tool("uv") {
+            Self::Uv
+        } else if root.join("poetry.lock").exists() || has_tool("poetry") {
+            Self::Poetry
+        } else if root.join("pdm.lock").exists() || has_tool("pdm") {
+            Self::Pdm
+        } else if root.join("hatch.toml").exists() || has_tool("hatch") {
+            Self::Hatch
+        } else if root.join("Pipfile").exists() {
+            Self::Pipenv
+        } else if let Some(venv) = [".venv", "venv"]
+            .iter()
+            .map(|dir| root.join(dir))
+            .find(|dir| dir.join("pyvenv.cfg").is_file())
+        {
+            Self::Venv(venv)
+        } else {
+            Self::System
+        }
+    }
 
-    let uses_uv = root.join("uv.lock").exists() || root.join("uv.toml").exists();
-    let command;
-    let mut args = Vec::new();
-    let reason;
-
-    if uses_uv {
-        command = "uv".to_string();
-        args.push("run".into());
-        args.push("python".into());
-        args.push("-m".into());
-        reason = "Detected pyproject with uv configuration".to_string();
-    } else {
-        command = "python".to_string();
-        args.push("-m".into());
-        reason = if has_pyproject {
-            "Detected pyproject.toml".to_string()
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Uv => "uv",
+            Self::Poetry => "Poetry",
+            Self::Pdm => "PDM",
+            Self::Hatch => "Hatch",
+            Self::Pipenv => "Pipenv",
+            Self::Venv(_) => "local virtualenv",
+            Self::System => "system Python",
+        }
+    }
+
+    /// Path of `name` inside the virtualenv's executables directory.
+    fn venv_executable(venv: &Path, name: &str) -> String {
+        let path = if cfg!(windows) {
+            venv.join("Scripts").join(format!("{name}.exe"))
         } else {
-            "Detected requirements.txt".to_string()
+            venv.join("bin").join(name)
         };
+        path_to_string(&path)
     }
 
-    args.push(module);
+    fn invocation(&self, program: PythonProgram) -> (String, Vec<String>) {
+        let (program, mut args) = match program {
+            PythonProgram::Python(args) => ("python".to_string(), args),
+            PythonProgram::Script(name) => (name, Vec::new()),
+        };
+        let tool = match self {
+            Self::Uv => "uv",
+            Self::Poetry => "poetry",
+            Self::Pdm => "pdm",
+            Self::Hatch => "hatch",
+            Self::Pipenv => "pipenv",
+            Self::Venv(venv) => return (Self::venv_executable(venv, &program), args),
+            Self::System => return (program, args),
+        };
+        args.splice(0..0, ["run".to_string(), program]);
+        (tool.to_string(), args)
+    }
+}
 
-    let suggestion = RunSuggestion {
-        language: "python".into(),
-        command,
-        args,
-        env: HashMap::new(),
-        cwd: Some(path_to_string(root)),
-        reason,
-    };
+fn read_pyproject(root: &Path) -> Option<toml::Value> {
+    let contents = fs::read_to_string(root.join("pyproject.toml")).ok()?;
+    toml::from_str(&contents).ok()
+}
 
-    push_unique(suggestions, suggestion);
+/// Console scripts from `[project.scripts]` and `[tool.poetry.scripts]`.
+fn python_scripts(pyproject: &toml::Value) -> Vec<String> {
+    let project = pyproject
+        .get("project")
+        .and_then(|project| project.get("scripts"));
+    let poetry = pyproject
+        .get("tool")
+        .and_then(|tool| tool.get("poetry"))
+        .and_then(|poetry| poetry.get("scripts"));
+
+    let mut names: Vec<String> = [project, poetry]
+        .into_iter()
+        .flatten()
+        .filter_map(|scripts| scripts.as_table())
+        .flat_map(|scripts| scripts.keys().cloned())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
 }
 
-fn parse_python_module(root: &Path) -> Option<String> {
-    let pyproject_path = root.join("pyproject.toml");
-    let contents = fs::read_to_string(pyproject_path).ok()?;
-    let value: toml::Value = toml::from_str(&contents).ok()?;
+/// Packages with a `__main__.py`, at the root or in a `src` layout.
+fn python_main_packages(root: &Path) -> Vec<String> {
+    let mut packages = Vec::new();
+    for dir in [root.to_path_buf(), root.join("src")] {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if !name.starts_with('.') && path.join("__main__.py").is_file() {
+                packages.push(name.to_string());
+            }
+        }
+    }
+    packages.sort();
+    packages.dedup();
+    packages
+}
 
-    if let Some(name) = value
-        .get("project")
-        .and_then(|project| project.get("name"))
-        .and_then(|name| name.as_str())
+/// Name bound to `constructor(...)` at the top level of a module, such as
+/// `app` in `app = FastAPI()`.
+fn python_app_variable(source: &str, constructor: &str) -> Option<String> {
+    source.lines().find_map(|line| {
+        let (name, value) = line.split_once('=')?;
+        let name = name.trim_end();
+        let value = value.trim_start();
+        let is_identifier = !name.is_empty()
+            && !name.starts_with(char::is_numeric)
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        (is_identifier
+            && value.starts_with(constructor)
+            && value[constructor.len()..].starts_with('('))
+        .then(|| name.to_string())
+    })
+}
+
+fn has_pytest_config(root: &Path, pyproject: Option<&toml::Value>) -> bool {
+    let in_pyproject = pyproject
+        .and_then(|value| value.get("tool"))
+        .and_then(|tool| tool.get("pytest"))
+        .is_some();
+    let in_ini = |file: &str, section: &str| {
+        fs::read_to_string(root.join(file))
+            .is_ok_and(|contents| contents.lines().any(|line| line.trim() == section))
+    };
+    in_pyproject
+        || root.join("pytest.ini").exists()
+        || root.join("conftest.py").exists()
+        || in_ini("tox.ini", "[pytest]")
+        || in_ini("setup.cfg", "[tool:pytest]")
+}
+
+fn detect_python(root: &Path, suggestions: &mut Vec<RunSuggestion>) {
+    if !PYTHON_PROJECT_FILES
+        .iter()
+        .any(|file| root.join(file).exists())
     {
-        return Some(name.replace('-', "_"));
+        return;
     }
 
-    value
-        .get("tool")
-        .and_then(|tool| tool.get("poetry"))
-        .and_then(|poetry| poetry.get("name"))
-        .and_then(|name| name.as_str())
-        .map(|name| name.replace('-', "_"))
-}
+    let pyproject = read_pyproject(root);
+    let runner = PythonRunner::detect(root, pyproject.as_ref());
+    let mut push = |program: PythonProgram, reason: String| {
+        let (command, args) = runner.invocation(program);
+        let suggestion = RunSuggestion {
+            language: "python".into(),
+            command,
+            args,
+            env: HashMap::new(),
+            cwd: Some(path_to_string(root)),
+            reason: format!("{} ({})", reason, runner.label()),
+        };
+        push_unique(suggestions, suggestion);
+    };
+    let python =
+        |args: &[&str]| PythonProgram::Python(args.iter().map(|a| a.to_string()).collect());
+
+    for script in pyproject.as_ref().map(python_scripts).unwrap_or_default() {
+        let reason = format!("Detected `{}` script in pyproject.toml", script);
+        push(PythonProgram::Script(script), reason);
+    }
 
-fn default_module_name(root: &Path) -> Option<String> {
-    root.file_name()
-        .and_then(|os| os.to_str())
-        .map(|name| name.replace('-', "_"))
+    if root.join("manage.py").is_file() {
+        push(
+            python(&["manage.py", "runserver"]),
+            "Detected Django manage.py".into(),
+        );
+    }
+
+    for module in PYTHON_APP_MODULES {
+        let Ok(source) = fs::read_to_string(root.join(format!("{module}.py"))) else {
+            continue;
+        };
+        if let Some(app) = python_app_variable(&source, "FastAPI") {
+            let target = format!("{module}:{app}");
+            push(
+                python(&["-m", "uvicorn", &target, "--reload"]),
+                format!("Detected FastAPI app `{}` in {}.py", app, module),
+            );
+        } else if let Some(app) = python_app_variable(&source, "Flask") {
+            let target = format!("{module}:{app}");
+            push(
+                python(&["-m", "flask", "--app", &target, "run", "--debug"]),
+                format!("Detected Flask app `{}` in {}.py", app, module),
+            );
+        }
+    }
+
+    for package in python_main_packages(root) {
+        push(
+            python(&["-m", &package]),
+            format!("Detected package `{}` with __main__.py", package),
+        );
+    }
+
+    if has_pytest_config(root, pyproject.as_ref()) {
+        push(
+            python(&["-m", "pytest"]),
+            "Detected pytest configuration".into(),
+        );
+    }
 }
 
 fn detect_gradle(root: &Path, suggestions: &mut Vec<RunSuggestion>) {
@@ -173,21 +646,236 @@ fn detect_gradle(root: &Path, suggestions: &mut Vec<RunSuggestion>) {
     push_unique(suggestions, suggestion);
 }
 
+/// Targets of one Cargo package, by kind.
+struct CargoPackage {
+    name: String,
+    default_run: Option<String>,
+    bins: Vec<String>,
+    examples: Vec<String>,
+    tests: Vec<String>,
+    benches: Vec<String>,
+}
+
+/// Names of the targets declared in `[[section]]` tables plus, unless
+/// `auto_key` is set to false, those Cargo discovers in `auto_dir`: every
+/// `*.rs` file and every subdirectory with a `main.rs`.
+fn cargo_targets(
+    dir: &Path,
+    manifest: &toml::Value,
+    section: &str,
+    auto_key: &str,
+    auto_dir: &str,
+) -> Vec<String> {
+    let mut names: Vec<String> = manifest
+        .get(section)
+        .and_then(|targets| targets.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|target| target.get("name").and_then(|name| name.as_str()))
+        .map(str::to_string)
+        .collect();
+
+    let auto = manifest
+        .get("package")
+        .and_then(|package| package.get(auto_key))
+        .and_then(|auto| auto.as_bool())
+        .unwrap_or(true);
+    if auto {
+        if let Ok(entries) = fs::read_dir(dir.join(auto_dir)) {
+            for entry in entries.filter_map(Result::ok) {
+                let path = entry.path();
+                let name = if path.is_dir() && path.join("main.rs").is_file() {
+                    path.file_name()
+                } else if path.extension().is_some_and(|ext| ext == "rs") {
+                    path.file_stem()
+                } else {
+                    None
+                };
+                if let Some(name) = name.and_then(|name| name.to_str()) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+    }
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn cargo_package(dir: &Path, manifest: &toml::Value) -> Option<CargoPackage> {
+    let package = manifest.get("package")?;
+    let name = package.get("name")?.as_str()?.to_string();
+
+    let mut bins = cargo_targets(dir, manifest, "bin", "autobins", "src/bin");
+    let autobins = package
+        .get("autobins")
+        .and_then(|auto| auto.as_bool())
+        .unwrap_or(true);
+    if autobins && dir.join("src/main.rs").is_file() && !bins.contains(&name) {
+        bins.push(name.clone());
+        bins.sort();
+    }
+
+    Some(CargoPackage {
+        default_run: package
+            .get("default-run")
+            .and_then(|bin| bin.as_str())
+            .map(str::to_string),
+        bins,
+        examples: cargo_targets(dir, manifest, "example", "autoexamples", "examples"),
+        tests: cargo_targets(dir, manifest, "test", "autotests", "tests"),
+        benches: cargo_targets(dir, manifest, "bench", "autobenches", "benches"),
+        name,
+    })
+}
+
+fn read_cargo_manifest(dir: &Path) -> Option<toml::Value> {
+    let contents = fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+    toml::from_str(&contents).ok()
+}
+
+/// Suggestions for one package, run from the workspace root. `qualified`
+/// selects the package with `-p`, which a workspace with several packages
+/// needs.
+fn cargo_package_runs(
+    root: &Path,
+    package: &CargoPackage,
+    qualified: bool,
+    suggestions: &mut Vec<RunSuggestion>,
+) {
+    let cargo = |subcommand: &str, target: Option<(&str, &str)>, reason: String| {
+        let mut args = vec![subcommand.to_string()];
+        if qualified {
+            args.push("-p".into());
+            args.push(package.name.clone());
+        }
+        if let Some((flag, name)) = target {
+            args.push(flag.to_string());
+            args.push(name.to_string());
+        }
+        RunSuggestion {
+            language: "rust".into(),
+            command: "cargo".into(),
+            args,
+            env: HashMap::new(),
+            cwd: Some(path_to_string(root)),
+            reason,
+        }
+    };
+    let mut push = |suggestion| push_unique(suggestions, suggestion);
+
+    // A bare `cargo run` is ambiguous when a package has several binaries
+    // and no `default-run`.
+    if let Some(bin) = package.default_run.as_deref() {
+        push(cargo(
+            "run",
+            None,
+            format!(
+                "Detected `default-run = \"{}\"` in package `{}`",
+                bin, package.name
+            ),
+        ));
+    } else if let [bin] = package.bins.as_slice() {
+        push(cargo(
+            "run",
+            None,
+            format!("Detected binary `{}` in package `{}`", bin, package.name),
+        ));
+    }
+    if package.bins.len() > 1 {
+        for bin in &package.bins {
+            push(cargo(
+                "run",
+                Some(("--bin", bin)),
+                format!("Detected binary `{}` in package `{}`", bin, package.name),
+            ));
+        }
+    }
+
+    for example in &package.examples {
+        push(cargo(
+            "run",
+            Some(("--example", example)),
+            format!(
+                "Detected example `{}` in package `{}`",
+                example, package.name
+            ),
+        ));
+    }
+    for test in &package.tests {
+        push(cargo(
+            "test",
+            Some(("--test", test)),
+            format!(
+                "Detected integration test `{}` in package `{}`",
+                test, package.name
+            ),
+        ));
+    }
+    for bench in &package.benches {
+        push(cargo(
+            "bench",
+            Some(("--bench", bench)),
+            format!(
+                "Detected benchmark `{}` in package `{}`",
+                bench, package.name
+            ),
+        ));
+    }
+}
+
 fn detect_rust(root: &Path, suggestions: &mut Vec<RunSuggestion>) {
     if !root.join("Cargo.toml").exists() {
         return;
     }
 
-    let suggestion = RunSuggestion {
-        language: "rust".into(),
-        command: "cargo".into(),
-        args: vec!["run".into()],
-        env: HashMap::new(),
-        cwd: Some(path_to_string(root)),
-        reason: "Detected Cargo.toml".into(),
+    let Some(manifest) = read_cargo_manifest(root) else {
+        // Unreadable manifests still get the plain suggestion; cargo will
+        // report what is wrong with them.
+        push_unique(
+            suggestions,
+            RunSuggestion {
+                language: "rust".into(),
+                command: "cargo".into(),
+                args: vec!["run".into()],
+                env: HashMap::new(),
+                cwd: Some(path_to_string(root)),
+                reason: "Detected Cargo.toml".into(),
+            },
+        );
+        return;
     };
 
-    push_unique(suggestions, suggestion);
+    let mut packages: Vec<CargoPackage> = cargo_package(root, &manifest).into_iter().collect();
+    let virtual_workspace = packages.is_empty();
+
+    if let Some(workspace) = manifest.get("workspace") {
+        let patterns = |key: &str| -> Vec<String> {
+            workspace
+                .get(key)
+                .and_then(|list| list.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|pattern| pattern.as_str())
+                .map(str::to_string)
+                .collect()
+        };
+        let mut members = patterns("members");
+        members.extend(patterns("exclude").into_iter().map(|dir| format!("!{dir}")));
+        for dir in workspace_packages(root, &members, "Cargo.toml") {
+            if let Some(package) =
+                read_cargo_manifest(&dir).and_then(|manifest| cargo_package(&dir, &manifest))
+            {
+                packages.push(package);
+            }
+        }
+    }
+
+    let qualified = virtual_workspace || packages.len() > 1;
+    for package in &packages {
+        cargo_package_runs(root, package, qualified, suggestions);
+    }
 }
 
 fn detect_go(root: &Path, suggestions: &mut Vec<RunSuggestion>) {
@@ -0,0 +1,35 @@
+use desktop_lib::bisect_runner::{run_from_args, ABORT_CODE, EXEC_ARG};
+
+fn args(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[test]
+fn bisect_runner_ignores_other_invocations() {
+    assert_eq!(run_from_args(&args(&["desktop"])), None);
+    assert_eq!(run_from_args(&args(&["desktop", "ssh-askpass"])), None);
+}
+
+#[test]
+fn bisect_runner_aborts_without_a_command() {
+    assert_eq!(
+        run_from_args(&args(&["desktop", EXEC_ARG, "."])),
+        Some(ABORT_CODE)
+    );
+}
+
+#[test]
+fn bisect_runner_aborts_when_the_directory_is_missing() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let missing = dir.path().join("missing").to_string_lossy().to_string();
+    let code = run_from_args(&args(&["desktop", EXEC_ARG, &missing, "git", "--version"]));
+    assert_eq!(code, Some(ABORT_CODE));
+}
+
+#[test]
+fn bisect_runner_runs_the_command_in_the_directory() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let cwd = dir.path().to_string_lossy().to_string();
+    let code = run_from_args(&args(&["desktop", EXEC_ARG, &cwd, "git", "--version"]));
+    assert_eq!(code, Some(0));
+}
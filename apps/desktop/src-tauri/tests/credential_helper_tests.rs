@@ -0,0 +1,29 @@
+use desktop_lib::credential_helper::{attribute, format_attributes, parse_attributes};
+
+#[test]
+fn credential_attributes_round_trip() {
+    let input = "protocol=https\nhost=example.com:8443\nusername=octo\n\nignored=true\n";
+    let attributes = parse_attributes(input);
+
+    assert_eq!(attributes.len(), 3, "parsing should stop at the blank line");
+    assert_eq!(attribute(&attributes, "host"), Some("example.com:8443"));
+    assert_eq!(
+        format_attributes(&attributes),
+        "protocol=https\nhost=example.com:8443\nusername=octo\n"
+    );
+}
+
+#[test]
+fn credential_attributes_skip_values_with_newlines() {
+    let attributes = vec![
+        ("username".to_string(), "octo".to_string()),
+        ("password".to_string(), "line\nbreak".to_string()),
+    ];
+    assert_eq!(format_attributes(&attributes), "username=octo\n");
+}
+
+#[test]
+fn non_helper_arguments_start_the_app() {
+    let args = vec!["desktop".to_string()];
+    assert_eq!(desktop_lib::credential_helper::run_from_args(&args), None);
+}
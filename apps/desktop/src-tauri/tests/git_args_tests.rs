@@ -0,0 +1,143 @@
+use desktop_lib::git_args::{check_config_key, check_config_value, check_run_args, fetch_args};
+
+fn run_args(line: &str) -> Vec<String> {
+    line.split(' ').map(str::to_string).collect()
+}
+
+#[test]
+fn run_args_allow_ordinary_commands() {
+    for line in [
+        "status --porcelain",
+        "-c color.ui=never log --oneline -n 5",
+        "rebase -i HEAD~3",
+        "rebase -Xtheirs main",
+        "grep -in TODO",
+        "grep -e OPEN",
+        "commit -am fixed",
+        "diff HEAD~1..HEAD -- src/lib.rs",
+        "ls-remote -h origin",
+        "ls-files -o --exclude=*.o",
+        "blame --ignore-rev HEAD~1 README.md",
+    ] {
+        assert_eq!(check_run_args(&run_args(line)), Ok(()), "{line}");
+    }
+}
+
+#[test]
+fn run_args_reject_denied_options_in_short_bundles() {
+    for line in [
+        "rebase -x make",
+        "rebase -ix make",
+        "rebase -ixmake",
+        "grep -O less",
+        "grep -iOless TODO",
+        "ls-remote -hu /tmp/prog origin",
+        "commit -t /etc/passwd",
+        "commit -aF /etc/passwd",
+        "tag -F /etc/passwd v1",
+    ] {
+        assert!(check_run_args(&run_args(line)).is_err(), "{line}");
+    }
+}
+
+#[test]
+fn run_args_reject_reading_outside_the_repository() {
+    for line in [
+        "diff --no-index /etc/passwd /dev/null",
+        "diff --no-ind /etc/passwd /dev/null",
+        "grep --no-index secret",
+        "diff /etc/passwd /dev/null",
+        "diff -- ../other/file src/lib.rs",
+        "commit --file=/etc/passwd",
+        "commit --template /etc/passwd",
+        "blame --contents /etc/passwd README.md",
+        "blame --ignore-revs-file /etc/passwd README.md",
+        "add --pathspec-from-file=/etc/passwd",
+        "restore --pathspec-from-file /etc/passwd",
+        "grep -f /etc/passwd",
+        "grep -if/etc/passwd",
+        "ls-files --exclude-from=/etc/passwd",
+        "ls-files --excludes-file=/etc/passwd",
+    ] {
+        assert!(check_run_args(&run_args(line)).is_err(), "{line}");
+    }
+}
+
+#[test]
+fn config_editor_accepts_ordinary_keys() {
+    for key in [
+        "user.name",
+        "user.signingkey",
+        "init.defaultBranch",
+        "pull.rebase",
+        "branch.main.remote",
+        "remote.origin.url",
+    ] {
+        assert_eq!(check_config_key(key), Ok(()), "{key}");
+    }
+}
+
+#[test]
+fn config_editor_rejects_keys_that_run_programs() {
+    for key in [
+        "core.sshCommand",
+        "core.pager",
+        "core.fsmonitor",
+        "core.hooksPath",
+        "diff.evil.command",
+        "diff.evil.textconv",
+        "filter.lfs.smudge",
+        "alias.st",
+        "include.path",
+        "includeIf.gitdir:~/work/.path",
+        "credential.https://example.com.helper",
+        "remote.origin.uploadpack",
+        "gpg.ssh.defaultKeyCommand",
+        "submodule.vendor/lib.update",
+        "mergetool.vimdiff.path",
+        "difftool.meld.path",
+        "core.worktree",
+    ] {
+        assert!(check_config_key(key).is_err(), "{key}");
+    }
+}
+
+#[test]
+fn config_editor_rejects_option_like_values() {
+    assert_eq!(check_config_value("Octo Cat"), Ok(()));
+    assert!(check_config_value("--global").is_err());
+    assert!(check_config_value("-x").is_err());
+    assert!(check_config_value("nul\0byte").is_err());
+}
+
+#[test]
+fn fetch_args_name_remote_branch_and_refspecs() {
+    let refspecs = vec!["+refs/heads/*:refs/remotes/origin/*".to_string()];
+    assert_eq!(
+        fetch_args(Some("origin"), Some("main"), true, false, &refspecs),
+        Ok(vec![
+            "fetch".to_string(),
+            "--prune".to_string(),
+            "origin".to_string(),
+            "main".to_string(),
+            "+refs/heads/*:refs/remotes/origin/*".to_string(),
+        ])
+    );
+    assert_eq!(
+        fetch_args(None, None, false, true, &[]),
+        Ok(vec![
+            "fetch".to_string(),
+            "--tags".to_string(),
+            "--all".to_string()
+        ])
+    );
+}
+
+#[test]
+fn fetch_args_reject_option_like_values() {
+    let refspecs = vec!["--upload-pack=touch /tmp/pwned".to_string()];
+    assert!(fetch_args(Some("origin"), None, false, false, &refspecs).is_err());
+    assert!(fetch_args(Some("--upload-pack=sh"), None, false, false, &[]).is_err());
+    assert!(fetch_args(Some("origin"), Some("-h"), false, false, &[]).is_err());
+    assert!(fetch_args(None, Some("main"), false, false, &[]).is_err());
+}
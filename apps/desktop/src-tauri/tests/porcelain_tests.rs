@@ -0,0 +1,27 @@
+use desktop_lib::porcelain::{changed_paths, StatusEntry};
+
+fn entry(code: &str, path: &str) -> StatusEntry {
+    StatusEntry {
+        code: code.to_string(),
+        path: path.to_string(),
+    }
+}
+
+#[test]
+fn porcelain_keeps_an_unstaged_first_entry_after_trimming() {
+    // Captured output is trimmed; the branch header keeps ` M` intact.
+    let output = "## main...origin/main\0 M README.md\0M  src/lib.rs\0".trim();
+    assert_eq!(
+        changed_paths(output),
+        vec![entry(" M", "README.md"), entry("M ", "src/lib.rs")]
+    );
+}
+
+#[test]
+fn porcelain_skips_the_original_path_of_renames() {
+    let output = "## HEAD (no branch)\0R  new.rs\0old.rs\0 D gone.rs";
+    assert_eq!(
+        changed_paths(output),
+        vec![entry("R ", "new.rs"), entry(" D", "gone.rs")]
+    );
+}
@@ -17,3 +17,16 @@ fn migrations_include_run_status() {
         "expected run status migration to be registered"
     );
 }
+
+#[test]
+fn migrations_are_listed_in_version_order() {
+    let versions: Vec<i64> = migrations::definitions()
+        .iter()
+        .map(|migration| migration.version)
+        .collect();
+    let expected: Vec<i64> = (1..=versions.len() as i64).collect();
+    assert_eq!(
+        versions, expected,
+        "migrations run in list order, so versions must be appended consecutively"
+    );
+}
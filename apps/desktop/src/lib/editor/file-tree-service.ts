@@ -12,6 +12,15 @@ export type TreeNode = {
   type: "file" | "directory";
   children?: TreeNode[];
   expanded?: boolean;
+  /** Tracked directory left out of the working tree by sparse checkout. */
+  sparseExcluded?: boolean;
+};
+
+type SparseCheckoutState = {
+  enabled: boolean;
+  /** Top of the worktree the excluded directories are relative to. */
+  root: string | null;
+  excludedDirectories: string[];
 };
 
 export type FileOpenResult = {
@@ -73,7 +82,8 @@ export class FileTreeService {
             .filter((entry) => this.shouldInclude(entry))
             .map(async (entry) => this.entryToNode(entry)),
         );
-        return this.sortNodes(children);
+        const excluded = await this.sparseExcludedDirectories(root);
+        return this.addSparsePlaceholders(root, this.sortNodes(children), excluded);
       },
       { root },
     );
@@ -135,6 +145,72 @@ export class FileTreeService {
     };
   }
 
+  // Paths come back relative to the repository root; keep those inside the
+  // project and make them relative to it, which may be a subdirectory.
+  private async sparseExcludedDirectories(root: string): Promise<string[]> {
+    try {
+      const state = await invoke<SparseCheckoutState>("git_sparse_checkout_list", {
+        repositoryPath: root,
+      });
+      if (!state.enabled || !state.root) {
+        return [];
+      }
+      const prefix = this.relativePath(state.root, root);
+      if (prefix === null) {
+        return [];
+      }
+      if (prefix === "") {
+        return state.excludedDirectories;
+      }
+      return state.excludedDirectories
+        .filter((path) => path.startsWith(`${prefix}/`))
+        .map((path) => path.slice(prefix.length + 1));
+    } catch (error) {
+      // not a git repository
+      return [];
+    }
+  }
+
+  private relativePath(base: string, path: string): string | null {
+    const normalize = (value: string) => value.replace(/\\/g, "/").replace(/\/+$/, "");
+    const from = normalize(base);
+    const to = normalize(path);
+    if (to === from) {
+      return "";
+    }
+    return to.startsWith(`${from}/`) ? to.slice(from.length + 1) : null;
+  }
+
+  // Excluded directories do not exist on disk; show them as placeholders
+  // under their (checked out) parent so they can be added to the sparse set.
+  private addSparsePlaceholders(root: string, nodes: TreeNode[], excluded: string[]): TreeNode[] {
+    for (const relative of excluded) {
+      const parts = relative.split("/");
+      const name = parts.pop() ?? relative;
+      let level: TreeNode[] | null = nodes;
+      let parentPath = root;
+      for (const part of parts) {
+        const parent: TreeNode | undefined = level?.find(
+          (node) => node.type === "directory" && node.name === part,
+        );
+        if (!parent) {
+          level = null;
+          break;
+        }
+        parent.children ??= [];
+        level = parent.children;
+        parentPath = parent.path;
+      }
+      if (!level || level.some((node) => node.name === name)) {
+        continue;
+      }
+      const path = `${parentPath}/${name}`;
+      level.push({ id: path, name, path, type: "directory", sparseExcluded: true });
+      this.sortNodes(level);
+    }
+    return nodes;
+  }
+
   private sortNodes(nodes: TreeNode[]): TreeNode[] {
     return nodes.sort((a, b) => {
       if (a.type !== b.type) {
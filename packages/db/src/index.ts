@@ -99,6 +99,11 @@ const settingsSchema = z.object({
   theme: z.string().nullable(),
   telemetryEnabled: z.boolean().nullable(),
   gitPath: z.string().nullable(),
+  gitUserName: z.string().nullable().optional(),
+  gitUserEmail: z.string().nullable().optional(),
+  gitDefaultBranch: z.string().nullable().optional(),
+  gitPullRebase: z.string().nullable().optional(),
+  gitSigningKey: z.string().nullable().optional(),
 });
 
 const editorFileStateSchema = z.object({
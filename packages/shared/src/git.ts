@@ -155,3 +155,27 @@ export const GitStreamEventSchema = z.object({
 });
 
 export type GitStreamEvent = z.infer<typeof GitStreamEventSchema>;
+
+export const GitCommitTemplateSchema = z.object({
+  path: z.string(),
+  content: z.string()
+});
+
+export type GitCommitTemplate = z.infer<typeof GitCommitTemplateSchema>;
+
+export const GitCommitLintWarningSchema = z.object({
+  rule: z.string(),
+  severity: z.enum(["error", "warning"]),
+  message: z.string(),
+  line: z.number().nullable()
+});
+
+export type GitCommitLintWarning = z.infer<typeof GitCommitLintWarningSchema>;
+
+export const GitCommitLintResultSchema = z.object({
+  valid: z.boolean(),
+  enabled: z.boolean(),
+  warnings: z.array(GitCommitLintWarningSchema)
+});
+
+export type GitCommitLintResult = z.infer<typeof GitCommitLintResultSchema>;